@@ -0,0 +1,177 @@
+use std::io::Stdout;
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+
+use crate::{
+    components::Component,
+    probability::{self, Polynomial, Probability, Total},
+    roll::{DiceGroup, Roller, Summary},
+};
+
+// A round of d20 attacks against an Armor Class. A natural 20, or anything in an expanded
+// crit range, always hits and doubles the damage dice. A natural 1 always misses.
+pub struct Attack {
+    to_hit: DiceGroup,
+    damage: DiceGroup,
+    attacks: u16,
+    crit_range: u16,
+}
+
+impl Attack {
+    pub fn new(to_hit: DiceGroup, damage: DiceGroup, attacks: u16, crit_range: u16) -> Self {
+        Self {
+            to_hit,
+            damage,
+            attacks,
+            crit_range: crit_range.clamp(2, 20),
+        }
+    }
+
+    // Rolls every attack and the damage of those that hit, returning the rolls and the damage dealt.
+    pub fn roll(&self, ac: u16) -> (Summary, u16) {
+        let mut round = Summary::new();
+        let mut damage = 0;
+        for i in 1..=self.attacks {
+            let mut attack = Roller::roll_group(self.to_hit.clone(), u16::MAX);
+            attack.set_label(&format!("Attack {}", i));
+            attack.hide_hits();
+            let natural = attack
+                .get_results()
+                .iter()
+                .find(|result| !result.dropped)
+                .map(|result| result.value - result.modifier)
+                .unwrap_or(1);
+            let crit = natural >= self.crit_range;
+            let hit = crit || (natural > 1 && attack.total >= ac);
+            attack.add_note(if crit {
+                "Critical hit!".to_string()
+            } else if natural == 1 {
+                "Natural 1: miss".to_string()
+            } else if hit {
+                format!("Hits AC {}", ac)
+            } else {
+                format!("Misses AC {}", ac)
+            });
+            round += attack;
+            if hit {
+                let mut dice = self.damage.clone();
+                if crit {
+                    dice.adjust_pool("Critical hit", dice.get_count() as i16);
+                }
+                let mut summary = Roller::roll_group(dice, u16::MAX);
+                summary.set_label(&format!("Damage {}", i));
+                summary.hide_hits();
+                damage += summary.total;
+                round += summary;
+            }
+        }
+        (round, damage)
+    }
+
+    // The chance a single attack hits, and the chance it crits.
+    pub fn get_hit_chances(&self, ac: u16) -> (f64, f64) {
+        let totals = Total::from_dice(&self.to_hit);
+        let mut hit = 0.;
+        let mut crit = 0.;
+        for natural in 2..=20 {
+            let p = totals.get_probability_of(natural);
+            if natural >= self.crit_range {
+                crit += p;
+            } else if self.to_hit.get_total(natural) >= ac {
+                hit += p;
+            }
+        }
+        (hit + crit, crit)
+    }
+
+    // The exact distribution of the damage dealt over the whole round.
+    pub fn get_damage(&self, ac: u16) -> Polynomial {
+        let (hit, crit) = self.get_hit_chances(ac);
+        let sides = self.damage.get_sides().unwrap_or(0);
+        let count = self.damage.get_count();
+        let mut die = Polynomial::new();
+        for face in 1..=sides {
+            die.set_coefficient(face, 1. / sides as f64);
+        }
+
+        let mut miss = Polynomial::new();
+        miss.set_coefficient(0, 1. - hit);
+        let mut normal = Polynomial::new();
+        normal.set_coefficient(0, hit - crit);
+        let mut critical = Polynomial::new();
+        critical.set_coefficient(0, crit);
+        let single = miss
+            + normal * probability::apply_modifiers(&die.pow(count), &self.damage)
+            + critical * probability::apply_modifiers(&die.pow(count * 2), &self.damage);
+        single.pow(self.attacks)
+    }
+}
+
+pub fn get_expected(damage: &Polynomial) -> f64 {
+    damage
+        .get_coefficients()
+        .iter()
+        .map(|(damage, p)| *damage as f64 * p)
+        .sum()
+}
+
+// Expected damage per round for every Armor Class in a range.
+pub struct AttackDisplay {
+    text: String,
+}
+
+impl AttackDisplay {
+    pub fn new(attack: &Attack, ac: u16, low: u16, high: u16) -> Self {
+        let mut text = format!("\n{}\n", "AC\tHit\t\tCrit\t\tExpected damage".bold());
+        for armor in low..=high {
+            let (hit, crit) = attack.get_hit_chances(armor);
+            let line = format!(
+                "{}\t{:>8.4} %\t{:>8.4} %\t{:.2}\n",
+                armor,
+                hit * 100.,
+                crit * 100.,
+                get_expected(&attack.get_damage(armor))
+            );
+            text += &if armor == ac {
+                format!("{}", line.bold())
+            } else {
+                line
+            };
+        }
+        Self { text }
+    }
+}
+
+impl Component for AttackDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_dice(dice: &str) -> DiceGroup {
+        DiceGroup::from(dice, 0, u16::MAX, false).unwrap()
+    }
+
+    #[test]
+    fn test_attack_penalty() {
+        let attack = Attack::new(get_dice("1d20-1"), get_dice("1d4-2"), 1, 20);
+        // a natural 11 or better hits AC 10 at -1, and the 20 crits
+        let (hit, crit) = attack.get_hit_chances(10);
+        assert!((hit - 0.5).abs() < 1e-12);
+        assert!((crit - 0.05).abs() < 1e-12);
+        // against AC 1 only a natural 1 misses: 1d4-2 averages 0.75 once it stops at 0,
+        // and the crit's 2d4-2 averages 3
+        let damage = attack.get_damage(1);
+        let total: f64 = damage.get_coefficients().values().sum();
+        assert!((total - 1.).abs() < 1e-12);
+        assert!((get_expected(&damage) - (0.9 * 0.75 + 0.05 * 3.)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,99 @@
+use crossterm::style::{StyledContent, Stylize};
+
+// Preset bands for games that share a reading of 2d6+stat: the name, the highest total of
+// every band but the last, then the label of every band from worst to best.
+const PRESETS: [(&str, &[u16], &[&str]); 4] = [
+    ("pbta", &[6, 9], &["Miss", "Weak hit", "Strong hit"]),
+    ("aw", &[6, 9], &["Miss", "Weak hit", "Strong hit"]),
+    ("dw", &[6, 9], &["Miss", "Partial success", "Full success"]),
+    (
+        "motw",
+        &[6, 9, 11],
+        &["Miss", "Mixed success", "Success", "Advanced success"],
+    ),
+];
+
+// Outcome bands for a total, like a PbtA miss on 6-, weak hit on 7-9 and strong hit on 10+.
+pub struct Bands {
+    limits: Vec<u16>,
+    labels: Vec<String>,
+}
+
+impl Bands {
+    // A preset name, or the highest total of each band like 6,9.
+    pub fn from(arg: &str) -> Option<Self> {
+        if let Some((_, limits, labels)) = PRESETS.iter().find(|(name, _, _)| *name == arg) {
+            return Some(Self {
+                limits: limits.to_vec(),
+                labels: labels.iter().map(|label| label.to_string()).collect(),
+            });
+        }
+        let mut limits = arg
+            .split(',')
+            .map(|limit| limit.trim().parse().ok())
+            .collect::<Option<Vec<u16>>>()?;
+        limits.sort_unstable();
+        limits.dedup();
+        let labels = match limits.len() {
+            1 => vec!["Miss".to_string(), "Hit".to_string()],
+            2 => vec![
+                "Miss".to_string(),
+                "Weak hit".to_string(),
+                "Strong hit".to_string(),
+            ],
+            len => (1..=len + 1).map(|band| format!("Band {}", band)).collect(),
+        };
+        Some(Self { limits, labels })
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn get_band(&self, total: u16) -> usize {
+        self.limits.iter().filter(|limit| total > **limit).count()
+    }
+
+    // The label of a band along with the totals it covers, like "Weak hit (7-9)".
+    pub fn get_label(&self, band: usize) -> String {
+        let range = match band {
+            0 => format!("{}-", self.limits[0]),
+            band if band == self.limits.len() => format!("{}+", self.limits[band - 1] + 1),
+            band if self.limits[band - 1] + 1 == self.limits[band] => {
+                format!("{}", self.limits[band])
+            }
+            band => format!("{}-{}", self.limits[band - 1] + 1, self.limits[band]),
+        };
+        format!("{} ({})", self.labels[band], range)
+    }
+
+    // The worst band is red and the best green, with those between shading from yellow to green.
+    pub fn get_style(&self, band: usize, text: String) -> StyledContent<String> {
+        match band {
+            0 => text.bold().dark_red(),
+            band if band + 1 == self.get_count() => text.bold().green(),
+            band if band * 2 >= self.get_count() => text.dark_green(),
+            _ => text.dark_yellow(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bands() {
+        let bands = Bands::from("9,6").unwrap();
+        assert_eq!(bands.get_band(6), 0);
+        assert_eq!(bands.get_band(7), 1);
+        assert_eq!(bands.get_band(10), 2);
+        assert_eq!(bands.get_label(1), "Weak hit (7-9)");
+        assert_eq!(bands.get_label(2), "Strong hit (10+)");
+        assert_eq!(
+            Bands::from("motw").unwrap().get_label(3),
+            "Advanced success (12+)"
+        );
+        assert!(Bands::from("six").is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use std::io::Stdout;
+
+use crossterm::{
+    queue,
+    style::{Print, StyledContent, Stylize},
+};
+
+use crate::components::Component;
+
+// The largest pool in the table of chances.
+const MAX_POOL: u16 = 6;
+
+// A Blades in the Dark action roll, read from the highest die alone. Worst first.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Bad,
+    Partial,
+    Full,
+    Critical,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [Action::Critical, Action::Full, Action::Partial, Action::Bad];
+
+    // 6 is a full success and two of them a critical, 4-5 is partial and 1-3 bad.
+    pub fn from(dice: &[u16]) -> Self {
+        let sixes = dice.iter().filter(|die| **die == 6).count();
+        match dice.iter().max() {
+            _ if sixes >= 2 => Self::Critical,
+            Some(6) => Self::Full,
+            Some(4..=5) => Self::Partial,
+            _ => Self::Bad,
+        }
+    }
+
+    pub fn get_label(&self) -> &str {
+        match self {
+            Self::Critical => "Critical success",
+            Self::Full => "Full success",
+            Self::Partial => "Partial success",
+            Self::Bad => "Bad outcome",
+        }
+    }
+
+    pub fn get_style(&self, text: String) -> StyledContent<String> {
+        match self {
+            Self::Critical => text.bold().green(),
+            Self::Full => text.dark_green(),
+            Self::Partial => text.dark_yellow(),
+            Self::Bad => text.bold().dark_red(),
+        }
+    }
+}
+
+// The exact chance of each outcome. A zero-dice pool rolls 2d6 and takes the lowest,
+// so it can never crit.
+pub fn get_chances(pool: u16) -> [f64; 4] {
+    let mut chances = [0.; 4];
+    if pool == 0 {
+        let at_least = |face: f64| ((7. - face) / 6.).powi(2);
+        chances[Action::Full as usize] = at_least(6.);
+        chances[Action::Partial as usize] = at_least(4.) - at_least(6.);
+        chances[Action::Bad as usize] = 1. - at_least(4.);
+        return chances;
+    }
+    let n = pool as i32;
+    let at_most = |face: f64| (face / 6.).powi(n);
+    let one_six = n as f64 / 6. * (5. / 6_f64).powi(n - 1);
+    chances[Action::Critical as usize] = (1. - at_most(5.) - one_six).max(0.);
+    chances[Action::Full as usize] = one_six;
+    chances[Action::Partial as usize] = at_most(5.) - at_most(3.);
+    chances[Action::Bad as usize] = at_most(3.);
+    chances
+}
+
+// The chance of every outcome for pools of zero to six dice.
+pub struct ActionDisplay {
+    text: String,
+}
+
+impl ActionDisplay {
+    pub fn new(pool: u16) -> Self {
+        let mut text = format!("\n{}\t", "Dice".bold());
+        for action in Action::ALL {
+            text += &format!(
+                "{}",
+                action.get_style(format!("{:<18}", action.get_label()))
+            );
+        }
+        text += "\n";
+        for size in 0..=MAX_POOL.max(pool) {
+            let chances = get_chances(size);
+            let mut line = format!("{}\t", size);
+            for action in Action::ALL {
+                line += &format!(
+                    "{:<18}",
+                    format!("{:>8.4} %", chances[action as usize] * 100.)
+                );
+            }
+            text += &if size == pool {
+                format!("{}\n", line.trim_end().bold())
+            } else {
+                format!("{}\n", line.trim_end())
+            };
+        }
+        Self { text }
+    }
+}
+
+impl Component for ActionDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chances() {
+        assert!((get_chances(1)[Action::Full as usize] - 1. / 6.).abs() < 1e-12);
+        assert_eq!(get_chances(1)[Action::Critical as usize], 0.);
+        assert!((get_chances(2)[Action::Critical as usize] - 1. / 36.).abs() < 1e-12);
+        assert!((get_chances(0)[Action::Bad as usize] - 27. / 36.).abs() < 1e-12);
+        for pool in 0..=MAX_POOL {
+            let total: f64 = get_chances(pool).iter().sum();
+            assert!((total - 1.).abs() < 1e-12);
+        }
+    }
+}
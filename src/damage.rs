@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+use crossterm::style::Stylize;
+
+use crate::state::State;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DamageType {
+    Physical,
+    Stun,
+}
+
+#[derive(Clone, Copy)]
+pub struct Damage {
+    pub amount: u16,
+    pub kind: DamageType,
+}
+
+impl Damage {
+    // Damage codes are written the way the books print them, e.g. 4P or 6S.
+    pub fn from(arg: &str) -> Option<Self> {
+        let arg = arg.trim().to_uppercase();
+        let kind = if arg.ends_with('P') {
+            DamageType::Physical
+        } else if arg.ends_with('S') {
+            DamageType::Stun
+        } else {
+            return None;
+        };
+        let amount = arg[..arg.len() - 1].parse::<u16>().ok()?;
+        Some(Self { amount, kind })
+    }
+}
+
+pub struct ConditionMonitor {
+    pub physical: u16,
+    pub stun: u16,
+    pub physical_boxes: u16,
+    pub stun_boxes: u16,
+    pub overflow: u16,
+}
+
+impl ConditionMonitor {
+    pub fn load(state: &State) -> Self {
+        Self {
+            physical: state.get("physical").unwrap_or(0),
+            stun: state.get("stun").unwrap_or(0),
+            physical_boxes: state.get("physical_boxes").unwrap_or(10),
+            stun_boxes: state.get("stun_boxes").unwrap_or(10),
+            overflow: state.get("overflow").unwrap_or(0),
+        }
+    }
+
+    pub fn store(&self, state: &mut State) {
+        state.set("physical", self.physical);
+        state.set("stun", self.stun);
+        state.set("physical_boxes", self.physical_boxes);
+        state.set("stun_boxes", self.stun_boxes);
+        state.set("overflow", self.overflow);
+    }
+
+    // Monitors have 8 boxes plus half the linked attribute, rounded up.
+    pub fn set_body(&mut self, body: u16) {
+        self.physical_boxes = 8 + body.div_ceil(2);
+        self.physical = self.physical.min(self.physical_boxes);
+    }
+
+    pub fn set_willpower(&mut self, willpower: u16) {
+        self.stun_boxes = 8 + willpower.div_ceil(2);
+        self.stun = self.stun.min(self.stun_boxes);
+    }
+
+    pub fn take(&mut self, damage: Damage) {
+        match damage.kind {
+            DamageType::Stun => {
+                self.stun += damage.amount;
+                if self.stun > self.stun_boxes {
+                    // every two boxes past a full stun track carry over as one box of physical
+                    let excess = self.stun - self.stun_boxes;
+                    self.stun = self.stun_boxes;
+                    self.take_physical(excess / 2);
+                }
+            }
+            DamageType::Physical => self.take_physical(damage.amount),
+        }
+    }
+
+    fn take_physical(&mut self, amount: u16) {
+        self.physical += amount;
+        if self.physical > self.physical_boxes {
+            self.overflow += self.physical - self.physical_boxes;
+            self.physical = self.physical_boxes;
+        }
+    }
+
+    pub fn heal(&mut self, damage: Option<Damage>) {
+        match damage {
+            None => {
+                self.physical = 0;
+                self.stun = 0;
+                self.overflow = 0;
+            }
+            Some(Damage {
+                amount,
+                kind: DamageType::Stun,
+            }) => self.stun = self.stun.saturating_sub(amount),
+            Some(Damage {
+                amount,
+                kind: DamageType::Physical,
+            }) => {
+                let healed_overflow = amount.min(self.overflow);
+                self.overflow -= healed_overflow;
+                self.physical = self.physical.saturating_sub(amount - healed_overflow);
+            }
+        }
+    }
+
+    // -1 die for every 3 boxes filled on either track
+    pub fn get_wound_modifier(&self) -> u16 {
+        self.physical / 3 + self.stun / 3
+    }
+}
+
+impl Display for ConditionMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Physical\t{} {}/{}",
+            get_track(self.physical, self.physical_boxes).dark_red(),
+            self.physical,
+            self.physical_boxes
+        )?;
+        writeln!(
+            f,
+            "Stun\t\t{} {}/{}",
+            get_track(self.stun, self.stun_boxes).dark_yellow(),
+            self.stun,
+            self.stun_boxes
+        )?;
+        if self.overflow > 0 {
+            writeln!(f, "{}\t{}", "Overflow".bold().dark_red(), self.overflow)?;
+        }
+        writeln!(f, "Wound modifier:\t-{}", self.get_wound_modifier())
+    }
+}
+
+fn get_track(filled: u16, boxes: u16) -> String {
+    let mut track = String::from("[");
+    for i in 0..boxes {
+        track.push(if i < filled { 'X' } else { '·' });
+    }
+    track.push(']');
+    track
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_monitor() -> ConditionMonitor {
+        ConditionMonitor {
+            physical: 0,
+            stun: 0,
+            physical_boxes: 10,
+            stun_boxes: 10,
+            overflow: 0,
+        }
+    }
+
+    #[test]
+    fn test_wound_modifier() {
+        assert!(Damage::from("4P").is_some_and(|damage| damage.kind == DamageType::Physical));
+        assert!(Damage::from("6s").is_some_and(|damage| damage.kind == DamageType::Stun));
+        assert!(Damage::from("4X").is_none());
+
+        // -1 for every 3 boxes on each track, counted separately
+        let mut monitor = get_monitor();
+        monitor.take(Damage::from("2P").unwrap());
+        assert_eq!(monitor.get_wound_modifier(), 0);
+        monitor.take(Damage::from("1P").unwrap());
+        assert_eq!(monitor.get_wound_modifier(), 1);
+        monitor.take(Damage::from("5S").unwrap());
+        assert_eq!(monitor.get_wound_modifier(), 2);
+        // 14 stun fills the track and carries 2 boxes over to physical
+        monitor.take(Damage::from("9S").unwrap());
+        assert_eq!((monitor.stun, monitor.physical), (10, 5));
+        assert_eq!(monitor.get_wound_modifier(), 4);
+        monitor.heal(None);
+        assert_eq!(monitor.get_wound_modifier(), 0);
+    }
+}
@@ -0,0 +1,204 @@
+use std::{collections::HashMap, fmt::Display, io::Stdout};
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+
+use crate::{
+    components::Component,
+    roll::{DiceGroup, Roller, Summary},
+    state::State,
+};
+
+// How a dying character's saves turn out once the tally is settled.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Revived,
+    Stable,
+    Dead,
+}
+
+// A 5e dying character's death saving throws: 10 or better is a success, a 1 counts as two
+// failures and a natural 20 brings them back with 1 hit point. Three of either settles it.
+pub struct DeathSaves {
+    pub successes: u16,
+    pub failures: u16,
+}
+
+impl DeathSaves {
+    pub fn load(state: &State) -> Self {
+        Self {
+            successes: state.get("death_successes").unwrap_or(0),
+            failures: state.get("death_failures").unwrap_or(0),
+        }
+    }
+
+    pub fn store(&self, state: &mut State) {
+        state.set("death_successes", self.successes);
+        state.set("death_failures", self.failures);
+    }
+
+    pub fn reset(&mut self) {
+        self.successes = 0;
+        self.failures = 0;
+    }
+
+    // Rolls the next save. Once the tally is settled it starts over for the next time they drop.
+    pub fn roll(&mut self) -> (Summary, Option<Outcome>) {
+        let dice = DiceGroup::from("1d20", 0, 10, false).unwrap_or_default();
+        let mut save = Roller::roll_group(dice, 10);
+        save.set_label("Death saving throw");
+        save.hide_hits();
+        let outcome = match save.total {
+            20 => Some(Outcome::Revived),
+            roll => {
+                if roll == 1 {
+                    self.failures += 2;
+                } else if roll >= 10 {
+                    self.successes += 1;
+                } else {
+                    self.failures += 1;
+                }
+                if self.failures >= 3 {
+                    Some(Outcome::Dead)
+                } else if self.successes >= 3 {
+                    Some(Outcome::Stable)
+                } else {
+                    None
+                }
+            }
+        };
+        save.add_note(match outcome {
+            Some(Outcome::Revived) => "Natural 20: back up with 1 hit point".to_string(),
+            Some(Outcome::Stable) => "Stable".to_string(),
+            Some(Outcome::Dead) => "Dead".to_string(),
+            None => format!("{}", self),
+        });
+        if outcome.is_some() {
+            self.reset();
+        }
+        (save, outcome)
+    }
+
+    // The chance of each outcome from the current tally, and by which save it has happened.
+    pub fn get_outcomes(&self) -> Vec<HashMap<Outcome, f64>> {
+        let mut states: HashMap<(u16, u16), f64> = HashMap::new();
+        states.insert((self.successes, self.failures), 1.);
+        let mut settled: HashMap<Outcome, f64> = HashMap::new();
+        let mut turns = vec![];
+        while !states.is_empty() {
+            let mut next = HashMap::new();
+            for ((successes, failures), p) in states {
+                let rolls = [
+                    (Some(Outcome::Revived), (successes, failures), 1.),
+                    (None, (successes + 1, failures), 10.),
+                    (None, (successes, failures + 1), 8.),
+                    (None, (successes, failures + 2), 1.),
+                ];
+                for (outcome, (s, f), faces) in rolls {
+                    let q = p * faces / 20.;
+                    let outcome = outcome.or(if f >= 3 {
+                        Some(Outcome::Dead)
+                    } else if s >= 3 {
+                        Some(Outcome::Stable)
+                    } else {
+                        None
+                    });
+                    match outcome {
+                        Some(outcome) => *settled.entry(outcome).or_insert(0.) += q,
+                        None => *next.entry((s, f)).or_insert(0.) += q,
+                    }
+                }
+            }
+            states = next;
+            turns.push(settled.clone());
+        }
+        turns
+    }
+}
+
+impl Display for DeathSaves {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let marks = |count: u16| {
+            (0..3)
+                .map(|i| if i < count { '●' } else { '○' })
+                .collect::<String>()
+        };
+        write!(
+            f,
+            "Successes: {}  Failures: {}",
+            marks(self.successes),
+            marks(self.failures)
+        )
+    }
+}
+
+// The odds of reviving, stabilizing or dying, save by save, from the current tally.
+pub struct DeathSaveDisplay {
+    text: String,
+}
+
+impl DeathSaveDisplay {
+    pub fn new(saves: &DeathSaves) -> Self {
+        let turns = saves.get_outcomes();
+        let get = |turn: &HashMap<Outcome, f64>, outcome| *turn.get(&outcome).unwrap_or(&0.) * 100.;
+        let mut text = format!("\n{}\n", saves);
+        text += &format!("{}\n", "Save\tRevived\t\tStable\t\tDead".bold());
+        for (i, turn) in turns.iter().enumerate() {
+            text += &format!(
+                "{}\t{:>8.4} %\t{:>8.4} %\t{:>8.4} %\n",
+                i + 1,
+                get(turn, Outcome::Revived),
+                get(turn, Outcome::Stable),
+                get(turn, Outcome::Dead)
+            );
+        }
+        if let Some(last) = turns.last() {
+            text += &format!(
+                "\nProbability of reviving:\t{} %\nProbability of stabilizing:\t{} %\nProbability of dying:\t\t{} %\n",
+                format!("{:>8.4}", get(last, Outcome::Revived)).bold().green(),
+                format!("{:>8.4}", get(last, Outcome::Stable)).bold(),
+                format!("{:>8.4}", get(last, Outcome::Dead)).bold().dark_red()
+            );
+        }
+        Self { text }
+    }
+}
+
+impl Component for DeathSaveDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_death_saves() {
+        let saves = DeathSaves {
+            successes: 0,
+            failures: 0,
+        };
+        let turns = saves.get_outcomes();
+        let settled = turns.last().unwrap();
+        let total: f64 = settled.values().sum();
+        assert!((total - 1.).abs() < 1e-12);
+        // a natural 20 on the first save
+        assert!((turns[0][&Outcome::Revived] - 1. / 20.).abs() < 1e-12);
+        assert_eq!(turns.len(), 5);
+
+        // with two of each, 10 to 19 is stable and anything under 10 is death
+        let saves = DeathSaves {
+            successes: 2,
+            failures: 2,
+        };
+        let turns = saves.get_outcomes();
+        assert_eq!(turns.len(), 1);
+        assert!((turns[0][&Outcome::Dead] - 9. / 20.).abs() < 1e-12);
+        assert!((turns[0][&Outcome::Stable] - 10. / 20.).abs() < 1e-12);
+    }
+}
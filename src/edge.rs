@@ -0,0 +1,135 @@
+use std::io::Stdout;
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+
+use crate::{
+    components::Component,
+    probability::{Hits, Polynomial, Probability},
+    roll::{DiceGroup, Summary},
+};
+
+// Shadowrun 6e Edge boosts that are spent after the dice hit the table.
+#[derive(Clone, Copy, PartialEq)]
+pub enum EdgeBoost {
+    PlusOne,
+    RerollOne,
+    Push,
+}
+
+impl EdgeBoost {
+    pub fn from(arg: &str) -> Option<Self> {
+        match arg {
+            "plus-one" => Some(Self::PlusOne),
+            "reroll-one" => Some(Self::RerollOne),
+            "push" => Some(Self::Push),
+            _ => None,
+        }
+    }
+
+    pub fn apply(&self, summary: &mut Summary, edge: u16, sides: u16, success: u16) {
+        match self {
+            Self::PlusOne => summary.boost_die(success),
+            Self::RerollOne => summary.reroll_die(success),
+            Self::Push => summary.push_edge(edge, sides, success),
+        }
+    }
+}
+
+pub struct EdgeDisplay {
+    text: String,
+}
+
+impl EdgeDisplay {
+    pub fn new(dice: &DiceGroup, hitnum: Option<u16>, edge: u16) -> Self {
+        let target = hitnum.unwrap_or(1);
+        let hits = Hits::from_dice(dice);
+        let n = dice.get_count();
+        let sides = dice.get_sides().unwrap_or(1) as f64;
+        let hit = dice.get_hit();
+        let p_hit = hits_per_die(dice);
+        let plain = hits.get_probability_of_gt(target);
+
+        // one hit short: either a die sits one pip below a hit, or a missed die rerolls into one
+        let short = if target > 0 {
+            hits.get_probability_of(target - 1)
+        } else {
+            0.
+        };
+        let misses = n.saturating_sub(target.saturating_sub(1));
+        let p_near = if hit >= 2 && hit - 1 <= sides as u16 && p_hit < 1. {
+            (1. / sides) / (1. - p_hit)
+        } else {
+            0.
+        };
+        let plus_one = plain + short * (1. - (1. - p_near).powi(misses as i32));
+        let reroll_one = plain + if misses > 0 { short * p_hit } else { 0. };
+
+        let mut text = format!("\nEdge boosts for {} hits:\n", target);
+        text += &get_line("No boost:\t\t", plain, plain);
+        text += &get_line("+1 to a die:\t\t", plus_one, plain);
+        text += &get_line("Reroll one die:\t\t", reroll_one, plain);
+        if edge > 0 {
+            let mut base = Polynomial::new();
+            for x in 0..=n {
+                base.set_coefficient(x, hits.get_probability_of(x));
+            }
+            let pushed = base * get_exploding_hits(1. / sides, p_hit).pow(edge);
+            let push = pushed
+                .get_coefficients()
+                .iter()
+                .filter(|(x, _)| **x >= target)
+                .map(|(_, p)| p)
+                .sum();
+            text += &get_line(&format!("Add {} Edge dice:\t", edge), push, plain);
+        }
+        Self { text }
+    }
+}
+
+impl Component for EdgeDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+fn get_line(label: &str, chance: f64, plain: f64) -> String {
+    let shift = format!("({:+.4})", (chance - plain) * 100.);
+    format!(
+        "{}{} % {}\n",
+        label,
+        format!("{:>8.4}", chance * 100.).bold(),
+        if chance > plain {
+            shift.green()
+        } else {
+            shift.reset()
+        }
+    )
+}
+
+fn hits_per_die(dice: &DiceGroup) -> f64 {
+    let sides = dice.get_sides().unwrap_or(1);
+    let hit = dice.get_hit();
+    if hit > sides {
+        0.
+    } else {
+        (sides - hit.max(1) + 1) as f64 / sides as f64
+    }
+}
+
+// Hits from one die whose highest face is a hit and rolls again.
+fn get_exploding_hits(p_max: f64, p_hit: f64) -> Polynomial {
+    let mut poly = Polynomial::new();
+    poly.set_coefficient(0, 1. - p_hit);
+    if p_hit == 0. {
+        return poly;
+    }
+    for k in 1..=30 {
+        let stop = (p_hit - p_max) + p_max * (1. - p_hit);
+        poly.set_coefficient(k, p_max.powi(k as i32 - 1) * stop);
+    }
+    poly
+}
@@ -0,0 +1,215 @@
+use std::{fmt::Display, io::Stdout};
+
+use crossterm::{
+    queue,
+    style::{Print, StyledContent, Stylize},
+};
+
+use crate::components::Component;
+
+// The adjective ladder from Terrible (-2) to Legendary (+8).
+const LADDER: [&str; 11] = [
+    "Terrible",
+    "Poor",
+    "Mediocre",
+    "Average",
+    "Fair",
+    "Good",
+    "Great",
+    "Superb",
+    "Fantastic",
+    "Epic",
+    "Legendary",
+];
+
+// Like "Good (+3)". Anything off either end of the ladder keeps the name at that end.
+pub fn get_ladder(value: i16) -> String {
+    let rung = (value + 2).clamp(0, LADDER.len() as i16 - 1) as usize;
+    format!("{} ({:+})", LADDER[rung], value)
+}
+
+// How a roll against opposition turns out, by the shifts it gets.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Fail,
+    Tie,
+    Succeed,
+    SucceedWithStyle,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 4] = [
+        Outcome::SucceedWithStyle,
+        Outcome::Succeed,
+        Outcome::Tie,
+        Outcome::Fail,
+    ];
+
+    pub fn from(shifts: i16) -> Self {
+        match shifts {
+            3.. => Self::SucceedWithStyle,
+            1..=2 => Self::Succeed,
+            0 => Self::Tie,
+            _ => Self::Fail,
+        }
+    }
+
+    pub fn get_label(&self) -> &str {
+        match self {
+            Self::SucceedWithStyle => "Succeed with style",
+            Self::Succeed => "Succeed",
+            Self::Tie => "Tie",
+            Self::Fail => "Fail",
+        }
+    }
+
+    pub fn get_style(&self, text: String) -> StyledContent<String> {
+        match self {
+            Self::SucceedWithStyle => text.bold().green(),
+            Self::Succeed => text.dark_green(),
+            Self::Tie => text.dark_yellow(),
+            Self::Fail => text.bold().dark_red(),
+        }
+    }
+}
+
+// Four Fudge dice, each -1, 0 or +1, added to a skill.
+pub struct FateRoll {
+    pub dice: Vec<i16>,
+    pub skill: i16,
+    pub opposition: Option<i16>,
+}
+
+impl FateRoll {
+    pub fn roll(skill: i16, opposition: Option<i16>) -> Self {
+        let dice = (0..4)
+            .map(|_| (rand::random::<u16>() % 3) as i16 - 1)
+            .collect();
+        Self {
+            dice,
+            skill,
+            opposition,
+        }
+    }
+
+    pub fn get_total(&self) -> i16 {
+        self.skill + self.dice.iter().sum::<i16>()
+    }
+}
+
+impl Display for FateRoll {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let faces: Vec<String> = self
+            .dice
+            .iter()
+            .map(|die| match die {
+                1 => format!("{}", "[+]".green()),
+                -1 => format!("{}", "[-]".dark_red()),
+                _ => "[ ]".to_string(),
+            })
+            .collect();
+        writeln!(f, "Dice:\t\t{}", faces.join(" "))?;
+        writeln!(
+            f,
+            "Total:\t\t{:+} {:+} = {}",
+            self.dice.iter().sum::<i16>(),
+            self.skill,
+            get_ladder(self.get_total()).bold()
+        )?;
+        if let Some(opposition) = self.opposition {
+            let shifts = self.get_total() - opposition;
+            let outcome = Outcome::from(shifts);
+            writeln!(
+                f,
+                "Against {}:\t{} ({} {})",
+                get_ladder(opposition),
+                outcome.get_style(outcome.get_label().to_string()),
+                shifts,
+                if shifts.abs() == 1 { "shift" } else { "shifts" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The exact chance of every 4dF total, from -4 to +4.
+pub fn get_distribution() -> [f64; 9] {
+    let mut ways = [0.; 9];
+    ways[4] = 1.;
+    for _ in 0..4 {
+        let mut next = [0.; 9];
+        for (i, p) in ways.iter().enumerate() {
+            for step in [-1, 0, 1] {
+                let j = i as i16 + step;
+                if (0..9).contains(&j) {
+                    next[j as usize] += p / 3.;
+                }
+            }
+        }
+        ways = next;
+    }
+    ways
+}
+
+// The ladder a skill reaches roll by roll, and the outcome against an opposition.
+pub struct FateDisplay {
+    text: String,
+}
+
+impl FateDisplay {
+    pub fn new(skill: i16, opposition: Option<i16>) -> Self {
+        let chances = get_distribution();
+        let mut text = format!("\n{}\n", "Roll\tResult\t\t\tProbability\tAt least".bold());
+        let mut at_least = 0.;
+        for (i, p) in chances.iter().enumerate().rev() {
+            let roll = i as i16 - 4;
+            at_least += p;
+            text += &format!(
+                "{:+}\t{:<20}\t{:>8.4} %\t{:>8.4} %\n",
+                roll,
+                get_ladder(skill + roll),
+                p * 100.,
+                at_least * 100.
+            );
+        }
+        if let Some(opposition) = opposition {
+            let mut outcomes = [0.; 4];
+            for (i, p) in chances.iter().enumerate() {
+                let shifts = skill + i as i16 - 4 - opposition;
+                outcomes[Outcome::from(shifts) as usize] += p;
+            }
+            text += &format!("\nAgainst {}:\n", get_ladder(opposition));
+            for outcome in Outcome::ALL {
+                text += &format!(
+                    "{}\t{} %\n",
+                    outcome.get_style(format!("{:<20}", outcome.get_label())),
+                    format!("{:>8.4}", outcomes[outcome as usize] * 100.).bold()
+                );
+            }
+        }
+        Self { text }
+    }
+}
+
+impl Component for FateDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fate() {
+        let chances = get_distribution();
+        assert!((chances[4] - 19. / 81.).abs() < 1e-12);
+        assert!((chances[8] - 1. / 81.).abs() < 1e-12);
+        assert_eq!(get_ladder(3), "Good (+3)");
+        assert_eq!(get_ladder(-4), "Terrible (-4)");
+        assert!(Outcome::from(3) == Outcome::SucceedWithStyle);
+        assert!(Outcome::from(0) == Outcome::Tie);
+    }
+}
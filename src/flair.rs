@@ -1,11 +1,14 @@
-// this will have all the animated silly things that don't have actual data
+// the animated silly things, dressed up around the real results of a simulated roll
 use crate::drawterm;
 use crate::name;
+use crate::roll::Summary;
 use crossterm::style::Stylize;
 use rand::{self, random};
 use std::{thread, time};
 
-pub fn print_silly_shit() {
+// Each status check reports a share of the rolled dice, failing when any of them glitched,
+// and each aggregation bar fills to the success probability of one dice group.
+pub fn print_silly_shit(reports: &[(Summary, f64)]) {
     let checks = [
         "Synchronized packet transmission...".to_string(),
         "Multiple hops through coNET...".to_string(),
@@ -21,17 +24,58 @@ pub fn print_silly_shit() {
         "Analyzing real-time TacCon data...".to_string(),
     ];
 
-    print_bar_checks(&["Establishing secure connection".to_string()]);
+    print_bar_checks(&["Establishing secure connection".to_string()], &[1.0]);
     print_name();
     let mut addr = print_qeh_link();
     println!("Confirming anonymizing techniques. If none of the following succeed, disconnect IMMEDIATELY!\n");
-    print_status_checks(&checks);
+    let glitches: Vec<bool> = reports
+        .iter()
+        .flat_map(|(summary, _)| summary.get_results().iter().map(|r| r.critfail))
+        .collect();
+    print_status_checks(&checks, &glitches);
     println!("\nBeginning TacCon aggregation sequence...");
-    print_bar_checks(&data);
-    print_qeh_break(&mut addr);
+    let chances: Vec<f64> = reports.iter().map(|(_, chance)| *chance).collect();
+    print_bar_checks(&data, &chances);
+    println!("\nIntercepted telemetry:");
+    for (summary, _) in reports {
+        print!("{}", summary);
+    }
+    print_qeh_break(&mut addr, "Broken Pipe");
     print_signature();
 }
 
+// The Matrix session display: the same QEH link is held open from run to run until it is dropped.
+pub fn print_matrix_link(link: &[u16]) {
+    print_qeh_established(&mut Address::from(link));
+}
+
+pub fn print_matrix_break(link: &[u16], reason: &str) {
+    print_qeh_break(&mut Address::from(link), reason);
+}
+
+pub fn print_overwatch(score: u16, convergence: u16) {
+    let mut bar = String::new();
+    for i in 0..convergence {
+        bar.push(if i < score { '|' } else { '·' });
+    }
+    let line = format!("Overwatch Score  [{}] {}/{}", bar, score, convergence);
+    if score >= convergence {
+        println!("{}", line.bold().dark_red());
+    } else if score * 4 >= convergence * 3 {
+        println!("{}", line.bold().dark_yellow());
+        println!(
+            "{}",
+            "GOD is closing in. Jack out before convergence!".dark_yellow()
+        );
+    } else {
+        println!("{}", line);
+    }
+}
+
+pub fn get_matrix_link() -> Vec<u16> {
+    Address::new().addrs
+}
+
 struct Address {
     addrs: Vec<u16>,
     index: u8,
@@ -50,6 +94,13 @@ impl Address {
         Self { addrs, index: 0 }
     }
 
+    pub fn from(link: &[u16]) -> Self {
+        Self {
+            addrs: link.to_vec(),
+            index: 0,
+        }
+    }
+
     pub fn next(&mut self) -> u16 {
         if self.index == u8::MAX {
             self.index = 0
@@ -60,15 +111,20 @@ impl Address {
     }
 }
 
-fn print_status_checks(checks: &[String]) {
+// The dice are dealt out over the checks, so a big pool takes no longer than a small one.
+fn print_status_checks(checks: &[String], glitches: &[bool]) {
     let len = get_max_length(checks);
-    for check in checks {
+    let share = glitches.len().div_ceil(checks.len().max(1)).max(1);
+    let failed = glitches
+        .chunks(share)
+        .map(|dice| dice.iter().any(|glitch| *glitch));
+    for (check, glitch) in checks.iter().zip(failed) {
         let millis = rand::random::<u16>() % 1000;
         let delay = time::Duration::from_millis(millis as u64);
         drawterm::print(check.to_string());
         print_buffer(len, check);
         thread::sleep(delay);
-        if rand::random::<u8>() % 100 < 90 {
+        if !glitch {
             drawterm::print_green("Ok".to_string());
         } else {
             drawterm::print_red("Error!".to_string());
@@ -77,14 +133,14 @@ fn print_status_checks(checks: &[String]) {
     }
 }
 
-fn print_bar_checks(checks: &[String]) {
+fn print_bar_checks(checks: &[String], fills: &[f64]) {
     let width = drawterm::get_width();
     let len = get_max_length(checks);
-    let mut barsize: u16 = width - (len as u16 + 4);
+    let mut barsize: u16 = width.saturating_sub(len as u16 + 14);
     barsize = if barsize > 40 { 40 } else { barsize };
-    for check in checks {
+    for (check, fill) in checks.iter().cycle().zip(fills) {
         print_check(check, len);
-        print_bar(barsize);
+        print_bar(barsize, *fill);
     }
 }
 
@@ -101,8 +157,9 @@ fn print_buffer(len: usize, check: &str) {
     drawterm::print(buffer);
 }
 
-fn print_bar(barsize: u16) {
-    for _ in 0..barsize {
+fn print_bar(barsize: u16, fill: f64) {
+    let filled = (barsize as f64 * fill).round() as u16;
+    for _ in 0..filled {
         let delay = time::Duration::from_millis(
             (rand::random::<u16>() % 100 + (100.0 / barsize as f32) as u16) as u64,
         );
@@ -111,7 +168,10 @@ fn print_bar(barsize: u16) {
         }
         drawterm::print("|".to_string());
     }
-    drawterm::print("\n".to_string());
+    for _ in filled..barsize {
+        drawterm::print(" ".to_string());
+    }
+    drawterm::print(format!(" {:>5.1}%\n", fill * 100.));
 }
 
 fn print_name() {
@@ -155,6 +215,11 @@ fn print_signature() {
 
 fn print_qeh_link() -> Address {
     let mut addr = Address::new();
+    print_qeh_established(&mut addr);
+    addr
+}
+
+fn print_qeh_established(addr: &mut Address) {
     let delay = time::Duration::from_millis(200);
     let now = chrono::Utc::now();
     let later = now
@@ -175,10 +240,9 @@ fn print_qeh_link() -> Address {
     thread::sleep(delay);
     println!("\t{}\n", format);
     thread::sleep(delay);
-    addr
 }
 
-fn print_qeh_break(addr: &mut Address) {
+fn print_qeh_break(addr: &mut Address, reason: &str) {
     let now = chrono::Utc::now();
     let delay = time::Duration::from_millis(200);
     let later = now
@@ -190,11 +254,12 @@ fn print_qeh_break(addr: &mut Address) {
     println!("\t<<<<< QEH Signal Invalid/Missing >>>>>");
     thread::sleep(delay);
     println!(
-        "\tDisconnected from {:0>4x}:{:0>4x}:{:0>4x}:{:0>4x} (Broken Pipe)",
+        "\tDisconnected from {:0>4x}:{:0>4x}:{:0>4x}:{:0>4x} ({})",
         addr.next(),
         addr.next(),
         addr.next(),
-        addr.next()
+        addr.next(),
+        reason
     );
     thread::sleep(delay);
     println!("\t{}\n", format);
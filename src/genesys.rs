@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+// The net symbols of one face or a whole roll. Failures cancel successes and threat cancels
+// advantage, so both are kept as one signed count. A triumph is also a success and a despair
+// also a failure, but neither is ever cancelled.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Symbols {
+    pub success: i16,
+    pub advantage: i16,
+    pub triumph: u16,
+    pub despair: u16,
+}
+
+impl Symbols {
+    const fn face(success: i16, advantage: i16, triumph: u16, despair: u16) -> Self {
+        Self {
+            success,
+            advantage,
+            triumph,
+            despair,
+        }
+    }
+
+    pub fn add(&self, other: &Symbols) -> Self {
+        Self {
+            success: self.success + other.success,
+            advantage: self.advantage + other.advantage,
+            triumph: self.triumph + other.triumph,
+            despair: self.despair + other.despair,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.success > 0
+    }
+
+    // Like "2 successes, 1 advantage, 1 triumph", or "blank" when everything cancels.
+    pub fn get_label(&self) -> String {
+        let mut parts = vec![];
+        match self.success {
+            1 => parts.push("1 success".to_string()),
+            -1 => parts.push("1 failure".to_string()),
+            s if s > 1 => parts.push(format!("{} successes", s)),
+            s if s < -1 => parts.push(format!("{} failures", -s)),
+            _ => {}
+        }
+        if self.advantage > 0 {
+            parts.push(format!("{} advantage", self.advantage));
+        } else if self.advantage < 0 {
+            parts.push(format!("{} threat", -self.advantage));
+        }
+        if self.triumph > 0 {
+            parts.push(format!("{} triumph", self.triumph));
+        }
+        if self.despair > 0 {
+            parts.push(format!("{} despair", self.despair));
+        }
+        if parts.is_empty() {
+            "blank".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+const BLANK: Symbols = Symbols::face(0, 0, 0, 0);
+const S: Symbols = Symbols::face(1, 0, 0, 0);
+const SS: Symbols = Symbols::face(2, 0, 0, 0);
+const A: Symbols = Symbols::face(0, 1, 0, 0);
+const AA: Symbols = Symbols::face(0, 2, 0, 0);
+const SA: Symbols = Symbols::face(1, 1, 0, 0);
+const TRIUMPH: Symbols = Symbols::face(1, 0, 1, 0);
+const F: Symbols = Symbols::face(-1, 0, 0, 0);
+const FF: Symbols = Symbols::face(-2, 0, 0, 0);
+const T: Symbols = Symbols::face(0, -1, 0, 0);
+const TT: Symbols = Symbols::face(0, -2, 0, 0);
+const FT: Symbols = Symbols::face(-1, -1, 0, 0);
+const DESPAIR: Symbols = Symbols::face(-1, 0, 0, 1);
+
+// The Genesys and Star Wars narrative dice, written 2A1P2D1S for two ability, one proficiency,
+// two difficulty and one setback die.
+#[derive(Clone, Copy, PartialEq)]
+pub enum SymbolDie {
+    Boost,
+    Ability,
+    Proficiency,
+    Setback,
+    Difficulty,
+    Challenge,
+}
+
+impl SymbolDie {
+    pub fn from(c: char) -> Option<Self> {
+        match c {
+            'B' => Some(Self::Boost),
+            'A' => Some(Self::Ability),
+            'P' => Some(Self::Proficiency),
+            'S' => Some(Self::Setback),
+            'D' => Some(Self::Difficulty),
+            'C' => Some(Self::Challenge),
+            _ => None,
+        }
+    }
+
+    pub fn get_label(&self) -> &str {
+        match self {
+            Self::Boost => "boost",
+            Self::Ability => "ability",
+            Self::Proficiency => "proficiency",
+            Self::Setback => "setback",
+            Self::Difficulty => "difficulty",
+            Self::Challenge => "challenge",
+        }
+    }
+
+    pub fn get_faces(&self) -> &'static [Symbols] {
+        match self {
+            Self::Boost => &[BLANK, BLANK, S, SA, AA, A],
+            Self::Ability => &[BLANK, S, S, SS, A, A, SA, AA],
+            Self::Proficiency => &[BLANK, S, S, SS, SS, A, SA, SA, SA, AA, AA, TRIUMPH],
+            Self::Setback => &[BLANK, BLANK, F, F, T, T],
+            Self::Difficulty => &[BLANK, F, FF, T, T, T, TT, FT],
+            Self::Challenge => &[BLANK, F, F, FF, FF, T, T, FT, FT, TT, TT, DESPAIR],
+        }
+    }
+
+    pub fn get_sides(&self) -> u16 {
+        self.get_faces().len() as u16
+    }
+}
+
+pub fn parse_symbol_dice(arg: &str) -> Option<Vec<SymbolDie>> {
+    let mut dice = vec![];
+    let mut count = String::new();
+    for c in arg.chars() {
+        if c.is_ascii_digit() {
+            count.push(c);
+            continue;
+        }
+        let die = SymbolDie::from(c)?;
+        let times: usize = if count.is_empty() {
+            1
+        } else {
+            count.parse().ok()?
+        };
+        count.clear();
+        dice.extend(std::iter::repeat_n(die, times));
+    }
+    if dice.is_empty() || !count.is_empty() {
+        return None;
+    }
+    Some(dice)
+}
+
+// The exact chance of every net result of the roll.
+pub fn get_distribution(dice: &[SymbolDie]) -> HashMap<Symbols, f64> {
+    let mut results = HashMap::new();
+    results.insert(Symbols::default(), 1.);
+    for die in dice {
+        let faces = die.get_faces();
+        let p = 1. / faces.len() as f64;
+        let mut next = HashMap::new();
+        for (symbols, q) in &results {
+            for face in faces {
+                *next.entry(symbols.add(face)).or_insert(0.) += q * p;
+            }
+        }
+        results = next;
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_dice() {
+        let dice = parse_symbol_dice("2A1P2D").unwrap();
+        assert_eq!(dice.len(), 5);
+        assert!(parse_symbol_dice("2X").is_none());
+
+        let ability = get_distribution(&[SymbolDie::Ability]);
+        let success: f64 = ability
+            .iter()
+            .filter(|(symbols, _)| symbols.is_success())
+            .map(|(_, p)| p)
+            .sum();
+        assert!((success - 0.5).abs() < 1e-12);
+        let total: f64 = get_distribution(&dice).values().sum();
+        assert!((total - 1.).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,325 @@
+use std::{fmt::Display, io::Stdout};
+
+use crossterm::{
+    queue,
+    style::{Print, StyledContent, Stylize},
+};
+
+use crate::{
+    components::Component,
+    roll::{Result, Summary},
+    state::State,
+};
+
+const MAX_MOMENTUM: i16 = 10;
+const MIN_MOMENTUM: i16 = -6;
+// where momentum goes back to once it is burned
+const RESET_MOMENTUM: i16 = 2;
+// an action score can never beat a challenge die of 10
+const MAX_SCORE: u16 = 10;
+const MAX_ADDS: u16 = 10;
+
+// How an action roll turns out. Worst first.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum Outcome {
+    Miss,
+    WeakHit,
+    StrongHit,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::StrongHit, Outcome::WeakHit, Outcome::Miss];
+
+    // Beating both challenge dice is a strong hit and beating one a weak hit.
+    pub fn from(score: u16, challenge: [u16; 2]) -> Self {
+        match challenge.iter().filter(|die| score > **die).count() {
+            2 => Self::StrongHit,
+            1 => Self::WeakHit,
+            _ => Self::Miss,
+        }
+    }
+
+    pub fn get_label(&self) -> &str {
+        match self {
+            Self::StrongHit => "Strong hit",
+            Self::WeakHit => "Weak hit",
+            Self::Miss => "Miss",
+        }
+    }
+
+    pub fn get_style(&self, text: String) -> StyledContent<String> {
+        match self {
+            Self::StrongHit => text.bold().green(),
+            Self::WeakHit => text.dark_yellow(),
+            Self::Miss => text.bold().dark_red(),
+        }
+    }
+}
+
+// The momentum track, from -6 to +10. It can be burned in place of the action score, and
+// while it is negative an action die showing the same number is cancelled.
+pub struct Momentum {
+    pub value: i16,
+}
+
+impl Momentum {
+    pub fn load(state: &State) -> Self {
+        Self {
+            value: state.get("momentum").unwrap_or(RESET_MOMENTUM),
+        }
+    }
+
+    pub fn store(&self, state: &mut State) {
+        state.set("momentum", self.value);
+    }
+
+    pub fn adjust(&mut self, delta: i16) {
+        self.value = (self.value + delta).clamp(MIN_MOMENTUM, MAX_MOMENTUM);
+    }
+
+    pub fn reset(&mut self) {
+        self.value = RESET_MOMENTUM;
+    }
+
+    // Negative momentum cancels an action die that matches it.
+    pub fn cancels(&self, action: u16) -> bool {
+        self.value < 0 && -self.value == action as i16
+    }
+}
+
+impl Display for Momentum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let track: String = (MIN_MOMENTUM..=MAX_MOMENTUM)
+            .map(|box_value| {
+                if box_value == self.value {
+                    '●'
+                } else {
+                    '○'
+                }
+            })
+            .collect();
+        write!(f, "Momentum: {:+}  {}", self.value, track)
+    }
+}
+
+// A d6 action die plus a stat and adds against two d10 challenge dice.
+pub struct ActionRoll {
+    pub action: u16,
+    pub challenge: [u16; 2],
+    pub modifier: u16,
+}
+
+impl ActionRoll {
+    pub fn roll(stat: u16, adds: u16) -> Self {
+        Self {
+            action: 1 + rand::random::<u16>() % 6,
+            challenge: [
+                1 + rand::random::<u16>() % 10,
+                1 + rand::random::<u16>() % 10,
+            ],
+            modifier: stat + adds,
+        }
+    }
+
+    pub fn get_score(&self, momentum: &Momentum) -> u16 {
+        let action = if momentum.cancels(self.action) {
+            0
+        } else {
+            self.action
+        };
+        (action + self.modifier).min(MAX_SCORE)
+    }
+
+    // Equal challenge dice make any outcome more dramatic.
+    pub fn is_match(&self) -> bool {
+        self.challenge[0] == self.challenge[1]
+    }
+
+    // Rolls the dice as a summary, burning momentum if asked and if it makes the outcome better.
+    pub fn resolve(&self, momentum: &mut Momentum, burn: bool) -> Summary {
+        let mut summary = Summary::new();
+        summary.set_label("Action roll");
+        summary.hide_total();
+        summary.hide_hits();
+        let cancelled = momentum.cancels(self.action);
+        summary.add_result(Result {
+            modifier: self.modifier,
+            dropped: cancelled,
+            ..Result::new(self.action, 6)
+        });
+        let score = self.get_score(momentum);
+        let mut outcome = Outcome::from(score, self.challenge);
+        let burned = Outcome::from(momentum.value.max(0) as u16, self.challenge);
+        let burn = burn && burned > outcome;
+        for die in self.challenge {
+            let beaten = if burn {
+                momentum.value > die as i16
+            } else {
+                score > die
+            };
+            summary.add_result(Result {
+                hit: beaten,
+                ..Result::new(die, 10)
+            });
+        }
+        if cancelled {
+            summary.add_note(format!(
+                "Momentum {} cancels the action die",
+                momentum.value
+            ));
+        }
+        let mut reason = format!("action score {}", score);
+        if burn {
+            summary.add_note(format!(
+                "Burned momentum {}, reset to {:+}",
+                momentum.value, RESET_MOMENTUM
+            ));
+            outcome = burned;
+            reason = format!("momentum {}", momentum.value);
+            momentum.reset();
+        }
+        let matched = if self.is_match() { " with a match" } else { "" };
+        summary.add_note(format!("{}{} ({})", outcome.get_label(), matched, reason));
+        summary
+    }
+}
+
+// The exact chance of every outcome, counting all 600 ways the dice can fall.
+pub fn get_chances(modifier: u16, momentum: &Momentum) -> [f64; 3] {
+    let mut chances = [0.; 3];
+    for action in 1..=6 {
+        for first in 1..=10 {
+            for second in 1..=10 {
+                let roll = ActionRoll {
+                    action,
+                    challenge: [first, second],
+                    modifier,
+                };
+                let outcome = Outcome::from(roll.get_score(momentum), roll.challenge);
+                chances[outcome as usize] += 1. / 600.;
+            }
+        }
+    }
+    chances
+}
+
+// The chance of each outcome when momentum is burned in place of the action score.
+pub fn get_burn_chances(momentum: &Momentum) -> [f64; 3] {
+    let mut chances = [0.; 3];
+    for first in 1..=10 {
+        for second in 1..=10 {
+            let outcome = Outcome::from(momentum.value.max(0) as u16, [first, second]);
+            chances[outcome as usize] += 1. / 100.;
+        }
+    }
+    chances
+}
+
+// The odds of an action roll for every stat plus adds, with the rolled one in bold.
+pub struct IronswornDisplay {
+    text: String,
+}
+
+impl IronswornDisplay {
+    pub fn new(modifier: u16, momentum: &Momentum) -> Self {
+        let mut text = format!("\n{}\n{}\t", momentum, "Stat+adds".bold());
+        for outcome in Outcome::ALL {
+            text += &format!(
+                "{}",
+                outcome.get_style(format!("{:<16}", outcome.get_label()))
+            );
+        }
+        text += "\n";
+        for adds in 0..=MAX_ADDS.max(modifier) {
+            let chances = get_chances(adds, momentum);
+            let mut line = format!("{}\t", adds);
+            for outcome in Outcome::ALL {
+                line += &format!(
+                    "{:<16}",
+                    format!("{:>8.4} %", chances[outcome as usize] * 100.)
+                );
+            }
+            text += &if adds == modifier {
+                format!("{}\n", line.trim_end().bold())
+            } else {
+                format!("{}\n", line.trim_end())
+            };
+        }
+        if momentum.value > 0 {
+            let burn = get_burn_chances(momentum);
+            text += &format!(
+                "\nBurning momentum {}:\tstrong hit {} %, weak hit {} %\n",
+                momentum.value,
+                format!("{:>8.4}", burn[Outcome::StrongHit as usize] * 100.).bold(),
+                format!("{:>8.4}", burn[Outcome::WeakHit as usize] * 100.).bold()
+            );
+        }
+        text += &format!(
+            "Probability of a match:\t{} %\n",
+            format!("{:>8.4}", 10.).bold()
+        );
+        Self { text }
+    }
+}
+
+impl Component for IronswornDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+// The odds of a yes from the oracle, from almost certain to a small chance.
+pub fn get_oracle_threshold(odds: &str) -> Option<u16> {
+    match odds {
+        "almost-certain" => Some(11),
+        "likely" => Some(26),
+        "fifty-fifty" => Some(51),
+        "unlikely" => Some(76),
+        "small-chance" => Some(91),
+        _ => None,
+    }
+}
+
+// Asks the oracle a yes or no question on a d100. Doubles mean an extreme result or a twist.
+pub fn ask_oracle(threshold: u16) -> Summary {
+    let roll = 1 + rand::random::<u16>() % 100;
+    let mut summary = Summary::new();
+    summary.set_label("Ask the oracle");
+    summary.hide_total();
+    summary.hide_hits();
+    summary.add_result(Result::new(roll, 100));
+    let answer = if roll >= threshold { "Yes" } else { "No" };
+    let doubles = roll.is_multiple_of(11) || roll == 100;
+    summary.add_note(if doubles {
+        format!("{}, with a twist (needed {}+)", answer, threshold)
+    } else {
+        format!("{} (needed {}+)", answer, threshold)
+    });
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_roll() {
+        let momentum = Momentum { value: 2 };
+        let chances = get_chances(0, &momentum);
+        let total: f64 = chances.iter().sum();
+        assert!((total - 1.).abs() < 1e-12);
+        // the bare action die has to beat both challenge dice: (0 + 1 + 4 + 9 + 16 + 25) / 600
+        assert!((chances[Outcome::StrongHit as usize] - 55. / 600.).abs() < 1e-12);
+
+        let negative = Momentum { value: -3 };
+        let roll = ActionRoll {
+            action: 3,
+            challenge: [2, 2],
+            modifier: 2,
+        };
+        assert_eq!(roll.get_score(&negative), 2);
+        assert!(roll.is_match());
+        assert!(Outcome::from(10, [10, 1]) == Outcome::WeakHit);
+    }
+}
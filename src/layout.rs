@@ -1,9 +1,27 @@
 use std::io::stdout;
 
+use crossterm::style::Stylize;
+
 use crate::{
+    attack::{self, Attack, AttackDisplay},
+    bands::Bands,
+    blades::ActionDisplay,
     components::Component,
-    probability::{self, HitsGraph, Probability, SummaryDisplay, TotalGraph},
-    roll::DiceGroup,
+    deathsave::{DeathSaveDisplay, DeathSaves},
+    edge::EdgeDisplay,
+    fate::FateDisplay,
+    ironsworn::{IronswornDisplay, Momentum},
+    magic::{DrainDisplay, Spell},
+    percentile::{Percentile, PercentileDisplay},
+    probability::{
+        self, BandDisplay, DegreeDisplay, HitsGraph, Probability, SummaryDisplay, TotalGraph,
+    },
+    roll::{DiceGroup, EmptyPool},
+    scatter::{Scatter, ScatterDisplay, ScatterGrid},
+    split::SplitDisplay,
+    stats::{Method, PointBuyDisplay},
+    team::Team,
+    wh40k::{Sequence, SequenceDisplay},
 };
 
 pub fn plot_dice_totals(dice: &DiceGroup, total: Option<u16>) {
@@ -18,11 +36,128 @@ pub fn plot_dice_hits(dice: &DiceGroup, nhits: Option<u16>) {
     hitsbox.draw(&stdout()).unwrap();
 }
 
-pub fn show_summary(dice: &DiceGroup, nhits: Option<u16>, ntotal: Option<u16>) {
-    let hitsum = SummaryDisplay::new(dice, nhits, ntotal);
+pub fn show_summary(
+    dice: &DiceGroup,
+    nhits: Option<u16>,
+    ntotal: Option<u16>,
+    dc: Option<u16>,
+    raises: u16,
+) {
+    let hitsum = SummaryDisplay::new(dice, nhits, ntotal, dc, raises);
     hitsum.draw(&stdout()).unwrap();
 }
 
+pub fn show_actions(pool: u16) {
+    let actions = ActionDisplay::new(pool);
+    actions.draw(&stdout()).unwrap();
+}
+
+pub fn show_bands(dice: &DiceGroup, bands: &Bands) {
+    let outcomes = BandDisplay::new(dice, bands);
+    outcomes.draw(&stdout()).unwrap();
+}
+
+pub fn show_degrees(dice: &DiceGroup, dc: u16) {
+    let degrees = DegreeDisplay::new(dice, dc);
+    degrees.draw(&stdout()).unwrap();
+}
+
+pub fn show_edge_boosts(dice: &DiceGroup, nhits: Option<u16>, edge: u16) {
+    let boosts = EdgeDisplay::new(dice, nhits, edge);
+    boosts.draw(&stdout()).unwrap();
+}
+
+pub fn show_drain(spell: &Spell, success: u16, wounds: u16, empty_pool: EmptyPool) {
+    let drain = DrainDisplay::new(spell, success, wounds, empty_pool);
+    drain.draw(&stdout()).unwrap();
+}
+
+pub fn show_splits(dice: &DiceGroup, targets: &[u16]) {
+    let splits = SplitDisplay::new(dice, targets);
+    splits.draw(&stdout()).unwrap();
+}
+
+pub fn plot_stats(method: &Method, min_total: u16) {
+    let scores = probability::Total::from_polynomial(method.get_score_distribution());
+    let scorebox = TotalGraph::new(scores, 0);
+    scorebox.draw(&stdout()).unwrap();
+    let points = PointBuyDisplay::new(method, min_total);
+    points.draw(&stdout()).unwrap();
+}
+
+pub fn show_death_saves(saves: &DeathSaves) {
+    let odds = DeathSaveDisplay::new(saves);
+    odds.draw(&stdout()).unwrap();
+}
+
+pub fn show_fate(skill: i16, opposition: Option<i16>) {
+    let ladder = FateDisplay::new(skill, opposition);
+    ladder.draw(&stdout()).unwrap();
+}
+
+pub fn show_ironsworn(modifier: u16, momentum: &Momentum) {
+    let odds = IronswornDisplay::new(modifier, momentum);
+    odds.draw(&stdout()).unwrap();
+}
+
+pub fn show_sequence(sequence: &Sequence) {
+    let unsaved = probability::Total::from_polynomial(sequence.get_unsaved());
+    let unsavedbox = TotalGraph::new(unsaved, 0);
+    unsavedbox.draw(&stdout()).unwrap();
+    let stages = SequenceDisplay::new(sequence);
+    stages.draw(&stdout()).unwrap();
+}
+
+pub fn show_percentile(test: &Percentile) {
+    let levels = PercentileDisplay::new(test);
+    levels.draw(&stdout()).unwrap();
+}
+
+pub fn plot_attack(attack: &Attack, ac: u16, low: u16, high: u16) {
+    let damage = attack.get_damage(ac);
+    let expected = attack::get_expected(&damage);
+    let damagebox = TotalGraph::new(probability::Total::from_polynomial(damage), 0);
+    damagebox.draw(&stdout()).unwrap();
+    println!(
+        "\nExpected damage per round against AC {}:\t{}",
+        ac,
+        format!("{:.2}", expected).bold()
+    );
+    let table = AttackDisplay::new(attack, ac, low, high);
+    table.draw(&stdout()).unwrap();
+}
+
+pub fn show_landing(scatter: &Scatter, landing: (i32, i32)) {
+    let grid = ScatterGrid::landing(scatter.get_max_distance(), landing);
+    grid.draw(&stdout()).unwrap();
+}
+
+pub fn plot_scatter(scatter: &Scatter) {
+    let grid = ScatterGrid::heatmap(&scatter.get_distribution(), scatter.get_max_distance());
+    grid.draw(&stdout()).unwrap();
+    let distances = ScatterDisplay::new(scatter);
+    distances.draw(&stdout()).unwrap();
+}
+
+pub fn plot_team_hits(team: &Team, nhits: Option<u16>) {
+    let prob = team.get_hits();
+    let success = nhits.map(|n| prob.get_probability_of_gt(n));
+    let hitsbox = HitsGraph::new(prob, nhits.unwrap_or_default());
+    hitsbox.draw(&stdout()).unwrap();
+    if let Some(success) = success {
+        println!(
+            "\nProbability of success:\t\t\t{} %",
+            format!("{:>8.4}", success * 100.).bold()
+        );
+    }
+    println!(
+        "Probability of a helper critical glitch:{} %",
+        format!("{:>8.4}", team.get_helper_critical_glitch() * 100.)
+            .bold()
+            .dark_red()
+    );
+}
+
 #[allow(dead_code)]
 pub fn demo() {
     todo!()
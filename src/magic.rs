@@ -0,0 +1,203 @@
+use std::io::Stdout;
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+
+use crate::{
+    components::Component,
+    probability::{Hits, Probability},
+    roll::{DiceGroup, EmptyPool, Roller, Summary},
+};
+
+// A spell cast at a given Force: the casting pool is limited by Force, the target may
+// resist, and the caster always resists drain. Drain turns physical when the casting
+// hits exceed the caster's Magic.
+pub struct Spell {
+    pub pool: u16,
+    pub force: u16,
+    pub magic: u16,
+    pub defense: Option<u16>,
+    pub drain_pool: u16,
+    pub drain_modifier: i16,
+}
+
+impl Spell {
+    pub fn get_drain_value(&self, force: u16) -> u16 {
+        (force as i16 + self.drain_modifier).max(2) as u16
+    }
+
+    pub fn cast(&self, success: u16, wounds: u16, empty_pool: EmptyPool) -> Summary {
+        let mut chain = Summary::new();
+
+        let casting = self.get_casting_dice(success, wounds, empty_pool);
+        let mut spellcasting = Roller::roll_group(casting, success);
+        spellcasting.set_label(&format!("Spellcasting (Force {})", self.force));
+        spellcasting.apply_limit(self.force);
+        let hits = spellcasting.hits;
+        chain += spellcasting;
+
+        if let Some(defense) = self.defense {
+            let mut resist = Roller::roll_group(get_dice(defense, success), success);
+            resist.set_label("Defense");
+            let net = hits.saturating_sub(resist.hits);
+            resist.add_note(if net > 0 {
+                format!("Net hits: {}", net)
+            } else {
+                "The spell is resisted".to_string()
+            });
+            chain += resist;
+        }
+
+        let drain_value = self.get_drain_value(self.force);
+        let mut drain = Roller::roll_group(get_dice(self.drain_pool, success), success);
+        drain.set_label(&format!("Drain resistance (DV {})", drain_value));
+        let taken = drain_value.saturating_sub(drain.hits);
+        let kind = if hits > self.magic { "P" } else { "S" };
+        drain.add_note(format!("Drain taken: {}{}", taken, kind));
+        chain += drain;
+        chain
+    }
+
+    // The casting pool once wounds and the empty pool rule have had their say.
+    fn get_casting_dice(&self, success: u16, wounds: u16, empty_pool: EmptyPool) -> DiceGroup {
+        let mut casting = get_dice(self.pool, success);
+        casting.apply_wounds(wounds);
+        casting.resolve_empty_pool(empty_pool);
+        casting
+    }
+}
+
+fn get_dice(count: u16, success: u16) -> DiceGroup {
+    DiceGroup::from(&format!("{}d6", count), 0, success, false).unwrap_or_default()
+}
+
+// The odds of every outcome of the chain at each Force up to twice the caster's Magic.
+pub struct DrainDisplay {
+    text: String,
+}
+
+impl DrainDisplay {
+    pub fn new(spell: &Spell, success: u16, wounds: u16, empty_pool: EmptyPool) -> Self {
+        let dice = spell.get_casting_dice(success, wounds, empty_pool);
+        let casting = Hits::from_dice(&dice);
+        let defense = spell
+            .defense
+            .map(|defense| Hits::from_dice(&get_dice(defense, success)));
+        let drain = Hits::from_dice(&get_dice(spell.drain_pool, success));
+
+        let mut text: String = dice
+            .get_adjustments()
+            .iter()
+            .map(|(label, delta)| format!("\n{}:\t\t{:+} dice", label, delta))
+            .collect();
+        text += &format!(
+            "\n{}\n",
+            "Force\tDV\tSuccess\t\tTake drain\tPhysical\tAvg drain".bold()
+        );
+        for force in 1..=(spell.magic * 2).max(1) {
+            let (success_chance, physical) =
+                get_casting_chances(spell, force, &casting, dice.get_count(), defense.as_ref());
+            let drain_value = spell.get_drain_value(force);
+            let (drain_chance, average) = get_drain_chances(&drain, drain_value);
+            let line = format!(
+                "{}\t{}\t{:>8.4} %\t{:>8.4} %\t{:>8.4} %\t{:.2}\n",
+                force,
+                drain_value,
+                success_chance * 100.,
+                drain_chance * 100.,
+                physical * 100.,
+                average
+            );
+            text += &if force == spell.force {
+                format!("{}", line.bold())
+            } else if force > spell.magic {
+                format!("{}", line.dark_red())
+            } else {
+                line
+            };
+        }
+        Self { text }
+    }
+}
+
+// The chance the spell succeeds at a Force, and the chance its drain turns physical.
+fn get_casting_chances(
+    spell: &Spell,
+    force: u16,
+    casting: &Hits,
+    pool: u16,
+    defense: Option<&Hits>,
+) -> (f64, f64) {
+    let mut success = 0.;
+    let mut physical = 0.;
+    for hits in 0..=pool {
+        let p = casting.get_probability_of(hits);
+        let limited = hits.min(force);
+        if limited > spell.magic {
+            physical += p;
+        }
+        success += p * match defense {
+            Some(defense) => (0..limited)
+                .map(|resisted| defense.get_probability_of(resisted))
+                .sum(),
+            None if limited > 0 => 1.,
+            None => 0.,
+        };
+    }
+    (success, physical)
+}
+
+// The chance of taking any drain, and the drain taken on average.
+fn get_drain_chances(drain: &Hits, drain_value: u16) -> (f64, f64) {
+    let mut chance = 0.;
+    let mut average = 0.;
+    for resisted in 0..drain_value {
+        let p = drain.get_probability_of(resisted);
+        chance += p;
+        average += p * (drain_value - resisted) as f64;
+    }
+    (chance, average)
+}
+
+impl Component for DrainDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spell_odds() {
+        let spell = Spell {
+            pool: 1,
+            force: 1,
+            magic: 0,
+            defense: Some(1),
+            drain_pool: 1,
+            drain_modifier: -3,
+        };
+        assert_eq!(spell.get_drain_value(4), 2);
+        assert_eq!(spell.get_drain_value(6), 3);
+        // one die hits on a 5 or 6, and the defense's die has to miss
+        let casting = Hits::from_dice(&get_dice(1, 5));
+        let defense = Hits::from_dice(&get_dice(1, 5));
+        let (success, physical) = get_casting_chances(&spell, 1, &casting, 1, Some(&defense));
+        assert!((success - 2. / 9.).abs() < 1e-12);
+        assert!((physical - 1. / 3.).abs() < 1e-12);
+        // DV 2 against one die: 2 drain two times in three, 1 drain otherwise
+        let (chance, average) = get_drain_chances(&Hits::from_dice(&get_dice(1, 5)), 2);
+        assert!((chance - 1.).abs() < 1e-12);
+        assert!((average - 5. / 3.).abs() < 1e-12);
+
+        let wounded = spell.get_casting_dice(5, 1, EmptyPool::AutoFail);
+        assert_eq!(wounded.get_count(), 0);
+        let chance = spell.get_casting_dice(5, 1, EmptyPool::ChanceDie);
+        assert_eq!(chance.get_count(), 1);
+    }
+}
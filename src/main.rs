@@ -1,22 +1,67 @@
 use std::io::stdout;
 
-use clap::{command, Arg, ArgAction, ArgMatches, Command};
+use attack::Attack;
+use bands::Bands;
+use clap::{command, parser::ValueSource, Arg, ArgAction, ArgMatches, Command};
 use crossterm::{
     queue,
     style::{Print, Stylize},
 };
-use layout::{plot_dice_hits, plot_dice_totals, show_summary};
-use roll::{DiceGroup, Roller, Summary};
+use damage::{ConditionMonitor, Damage};
+use deathsave::{DeathSaves, Outcome};
+use edge::EdgeBoost;
+use fate::FateRoll;
+use ironsworn::{ActionRoll, Momentum};
+use layout::{
+    plot_attack, plot_dice_hits, plot_dice_totals, plot_scatter, plot_stats, plot_team_hits,
+    show_actions, show_bands, show_death_saves, show_degrees, show_drain, show_edge_boosts,
+    show_fate, show_ironsworn, show_landing, show_percentile, show_sequence, show_splits,
+    show_summary,
+};
+use magic::Spell;
+use matrix::Overwatch;
+use percentile::{Percentile, System};
+use pool::Pool;
+use probability::SummaryDisplay;
+use roll::{DiceGroup, EmptyPool, Roller, Summary};
+use rules::Rules;
+use scatter::Scatter;
+use split::Split;
+use state::State;
+use stats::Method;
+use team::Team;
+use wh40k::{Reroll, Sequence};
 
+mod attack;
+mod bands;
+mod blades;
 mod components;
+mod damage;
+mod deathsave;
 mod drawterm;
+mod edge;
+mod fate;
 mod first_names;
 mod flair;
+mod genesys;
+mod ironsworn;
 mod last_names;
 mod layout;
+mod magic;
+mod matrix;
 mod name;
+mod percentile;
+mod pool;
 mod probability;
 mod roll;
+mod rules;
+mod scatter;
+mod split;
+mod state;
+mod stats;
+mod team;
+mod wh40k;
+mod yze;
 
 fn main() {
     let matches = get_matches();
@@ -28,6 +73,34 @@ fn main() {
         run_sim(SimArgs::show_hits(&matches));
     } else if matches.subcommand_matches("total").is_some() {
         run_sim(SimArgs::show_total(&matches));
+    } else if matches.subcommand_matches("damage").is_some() {
+        run_damage(&matches);
+    } else if matches.subcommand_matches("pool").is_some() {
+        run_pool(&matches);
+    } else if matches.subcommand_matches("team").is_some() {
+        run_team(&matches);
+    } else if matches.subcommand_matches("cast").is_some() {
+        run_cast(&matches);
+    } else if matches.subcommand_matches("stats-gen").is_some() {
+        run_stats(&matches);
+    } else if matches.subcommand_matches("deathsave").is_some() {
+        run_deathsave(&matches);
+    } else if matches.subcommand_matches("attack").is_some() {
+        run_attack(&matches);
+    } else if matches.subcommand_matches("percentile").is_some() {
+        run_percentile(&matches);
+    } else if matches.subcommand_matches("fate").is_some() {
+        run_fate(&matches);
+    } else if matches.subcommand_matches("ironsworn").is_some() {
+        run_ironsworn(&matches);
+    } else if matches.subcommand_matches("40k").is_some() {
+        run_40k(&matches);
+    } else if matches.subcommand_matches("scatter").is_some() {
+        run_scatter(&matches);
+    } else if matches.subcommand_matches("split").is_some() {
+        run_split(&matches);
+    } else if matches.subcommand_matches("matrix").is_some() {
+        run_matrix(&matches);
     } else {
         run_roll(&matches);
     }
@@ -42,6 +115,13 @@ struct SimArgs {
     pub no_shitty_crits: bool,
     pub numhits: Option<u16>,
     pub numtotal: Option<u16>,
+    pub wounds: u16,
+    pub empty_pool: EmptyPool,
+    pub rules: Rules,
+    pub edge: u16,
+    pub dc: Option<u16>,
+    pub raises: u16,
+    pub bands: Option<Bands>,
     pub dice_args: Vec<String>,
 }
 
@@ -76,6 +156,13 @@ impl SimArgs {
             no_shitty_crits: false,
             numhits,
             numtotal: None,
+            wounds: get_wounds(args),
+            empty_pool: get_empty_pool(args),
+            rules: get_rules(args),
+            edge: get_edge(args),
+            dc: get_dc(args),
+            raises: get_raises(args),
+            bands: get_bands(args),
             dice_args,
         }
     }
@@ -110,6 +197,13 @@ impl SimArgs {
             no_shitty_crits: false,
             numhits: None,
             numtotal,
+            wounds: get_wounds(args),
+            empty_pool: get_empty_pool(args),
+            rules: get_rules(args),
+            edge: get_edge(args),
+            dc: get_dc(args),
+            raises: get_raises(args),
+            bands: get_bands(args),
             dice_args,
         }
     }
@@ -151,31 +245,158 @@ impl From<&ArgMatches> for SimArgs {
             no_shitty_crits: matches.get_flag("NSC"),
             numhits,
             numtotal,
+            wounds: get_wounds(matches),
+            empty_pool: get_empty_pool(matches),
+            rules: get_rules(matches),
+            edge: get_edge(matches),
+            dc: get_dc(matches),
+            raises: get_raises(matches),
+            bands: get_bands(matches),
             dice_args,
         }
     }
 }
 
 fn run_sim(matches: SimArgs) {
-    if matches.print_bullshit {
-        flair::print_silly_shit();
-    }
-    for dice in matches.dice_args {
+    let mut groups = vec![];
+    for dice in &matches.dice_args {
         if dice.trim().is_empty() {
             show_dice_warning();
             continue;
         }
 
-        let d =
-            DiceGroup::from(&dice, 0, matches.success, matches.no_shitty_crits).unwrap_or_default();
+        let mut d =
+            DiceGroup::from(dice, 0, matches.success, matches.no_shitty_crits).unwrap_or_default();
+        d.apply_wounds(matches.wounds);
+        d.resolve_empty_pool(matches.empty_pool);
+        groups.push(d);
+    }
+    // PF2e reads a DC as degrees of success rather than the 5e pass or fail
+    let dc = matches.dc.filter(|_| matches.rules != Rules::Pf2e);
+    if matches.print_bullshit {
+        let reports: Vec<(Summary, f64)> = groups
+            .iter()
+            .map(|d| {
+                let mut roller =
+                    Roller::from_dice_group(d.clone(), u16::MAX, matches.success, u16::MAX);
+                roller.roll(matches.no_shitty_crits);
+                let chance =
+                    SummaryDisplay::new(d, matches.numhits, matches.numtotal, dc, matches.raises)
+                        .get_success_chance();
+                let mut summary = roller.get_summary();
+                if !d.get_symbol_dice().is_empty() {
+                    summary.check_symbols();
+                }
+                (summary, chance)
+            })
+            .collect();
+        flair::print_silly_shit(&reports);
+    }
+    for d in groups {
         if matches.show_total {
             plot_dice_totals(&d, matches.numtotal);
         }
         if matches.show_hits {
             plot_dice_hits(&d, matches.numhits);
         }
-        if matches.show_summary {
-            show_summary(&d, matches.numhits, matches.numtotal);
+        // bands and Blades read the total or the highest die, not hits or glitches
+        if matches.show_summary && matches.bands.is_none() && matches.rules != Rules::Blades {
+            show_summary(&d, matches.numhits, matches.numtotal, dc, matches.raises);
+        }
+        if let (Rules::Pf2e, Some(dc)) = (matches.rules, matches.dc) {
+            show_degrees(&d, dc);
+        }
+        if let Some(bands) = &matches.bands {
+            show_bands(&d, bands);
+        }
+        if matches.rules == Rules::Blades {
+            let pool = match d.get_empty_pool() {
+                Some(_) => 0,
+                None => d.get_count(),
+            };
+            show_actions(pool);
+        }
+        if matches.rules == Rules::Sr6 && d.is_hit_pool() {
+            show_edge_boosts(&d, matches.numhits, matches.edge);
+        }
+    }
+}
+
+struct RollArgs {
+    pub success: u16,
+    pub reroll: u16,
+    pub critval: u16,
+    pub no_shitty_crits: bool,
+    pub wounds: u16,
+    pub empty_pool: EmptyPool,
+    pub rules: Rules,
+    pub boosts: Vec<EdgeBoost>,
+    pub edge: u16,
+    pub dc: Option<u16>,
+    pub raises: u16,
+    pub bands: Option<Bands>,
+    pub push: bool,
+}
+
+impl RollArgs {
+    pub fn with_success(success: u16) -> Self {
+        Self {
+            success,
+            reroll: u16::MAX,
+            critval: u16::MAX,
+            no_shitty_crits: false,
+            wounds: 0,
+            empty_pool: EmptyPool::AutoFail,
+            rules: Rules::Sr5,
+            boosts: vec![],
+            edge: 0,
+            dc: None,
+            raises: 0,
+            bands: None,
+            push: false,
+        }
+    }
+}
+
+impl From<&ArgMatches> for RollArgs {
+    fn from(matches: &ArgMatches) -> Self {
+        let success: u16 = matches
+            .get_one::<String>("Success")
+            .unwrap_or(&u16::MAX.to_string())
+            .parse()
+            .unwrap_or(u16::MAX);
+        let reroll: u16 = matches
+            .get_one::<String>("Reroll")
+            .unwrap_or(&u16::MAX.to_string())
+            .parse()
+            .unwrap_or(u16::MAX);
+        let critval: u16 = matches
+            .get_one::<String>("Crit")
+            .unwrap_or(&u16::MAX.to_string())
+            .parse()
+            .unwrap_or(u16::MAX);
+        let rules = get_rules(matches);
+        let boosts = matches
+            .get_many::<String>("Boost")
+            .unwrap_or_default()
+            .filter_map(|boost| EdgeBoost::from(boost))
+            .collect();
+
+        Self {
+            success,
+            reroll,
+            critval,
+            // the nsc homebrew is a 5e house rule
+            no_shitty_crits: matches.get_flag("NSC") && rules == Rules::Sr5,
+            wounds: get_wounds(matches),
+            empty_pool: get_empty_pool(matches),
+            rules,
+            boosts,
+            edge: get_edge(matches),
+            dc: get_dc(matches),
+            raises: get_raises(matches),
+            bands: get_bands(matches),
+            push: matches.get_flag("Push"),
         }
     }
 }
@@ -183,22 +404,7 @@ fn run_sim(matches: SimArgs) {
 fn run_roll(matches: &ArgMatches) {
     let mut previous = Summary::new();
     let mut total = Summary::new();
-    let success: u16 = matches
-        .get_one::<String>("Success")
-        .unwrap_or(&u16::MAX.to_string())
-        .parse()
-        .unwrap_or(u16::MAX);
-    let reroll: u16 = matches
-        .get_one::<String>("Reroll")
-        .unwrap_or(&u16::MAX.to_string())
-        .parse()
-        .unwrap_or(u16::MAX);
-    let critval: u16 = matches
-        .get_one::<String>("Crit")
-        .unwrap_or(&u16::MAX.to_string())
-        .parse()
-        .unwrap_or(u16::MAX);
-    let no_shitty_crits = matches.get_flag("NSC");
+    let args: RollArgs = matches.into();
     let dice_args = matches.get_many::<String>("Dice").unwrap_or_else(|| {
         show_dice_warning();
         clap::parser::ValuesRef::default()
@@ -208,20 +414,556 @@ fn run_roll(matches: &ArgMatches) {
             show_dice_warning();
             continue;
         }
-        roll(
-            dice,
-            &mut previous,
-            success,
-            no_shitty_crits,
-            critval,
-            reroll,
-            &mut total,
-        );
+        roll(dice, &mut previous, &args, &mut total);
+    }
+
+    queue!(stdout(), Print(total)).ok();
+    if args.rules == Rules::Sr6 {
+        let attack_rating = matches.get_one::<String>("Attack Rating");
+        let defense_rating = matches.get_one::<String>("Defense Rating");
+        if let (Some(ar), Some(dr)) = (attack_rating, defense_rating) {
+            println!(
+                "{}",
+                rules::get_edge_award(ar.parse().unwrap_or(0), dr.parse().unwrap_or(0))
+            );
+        }
     }
+}
 
+fn run_pool(matches: &ArgMatches) {
+    let pool_match = matches.subcommand_matches("pool").unwrap();
+    let args: RollArgs = matches.into();
+    let expression = pool_match.get_one::<String>("Pool").unwrap();
+    let pool = match Pool::from(expression) {
+        Some(pool) => pool,
+        None => {
+            println!(
+                "\n{} '{}' is not a pool. Write each item as a name and a number of dice, like {}.\n",
+                "warning:".bold().dark_yellow(),
+                expression,
+                "\"agility:5 + pistols:6 - recoil:1\"".green()
+            );
+            return;
+        }
+    };
+    let sides: u16 = pool_match
+        .get_one::<String>("Sides")
+        .and_then(|sides| sides.parse().ok())
+        .unwrap_or(6);
+    let mut d = pool.to_dice_group(sides, args.success);
+    d.apply_wounds(args.wounds);
+    if let Some(bonus) = pool_match.get_one::<String>("Bonus") {
+        let bonus: i16 = bonus.parse().unwrap_or(0);
+        if bonus < 0 {
+            d.set_penalty(bonus.unsigned_abs());
+        } else {
+            d.set_modifier(bonus as u16);
+        }
+    }
+    d.resolve_empty_pool(args.empty_pool);
+    let mut roller = Roller::from_dice_group(d, args.critval, args.success, args.reroll);
+    roller.roll(args.no_shitty_crits);
+    let mut total = Summary::new();
+    total += roller.get_summary();
     queue!(stdout(), Print(total)).ok();
 }
 
+fn run_team(matches: &ArgMatches) {
+    let team_match = matches.subcommand_matches("team").unwrap();
+    let args: RollArgs = matches.into();
+    let success = if args.success == u16::MAX {
+        5
+    } else {
+        args.success
+    };
+    let mut leader = DiceGroup::from(
+        team_match.get_one::<String>("Leader").unwrap(),
+        0,
+        success,
+        args.no_shitty_crits,
+    )
+    .unwrap_or_default();
+    leader.apply_wounds(args.wounds);
+    let helpers = team_match
+        .get_many::<String>("Helper")
+        .unwrap_or_default()
+        .map(|helper| DiceGroup::from(helper, 0, success, args.no_shitty_crits).unwrap_or_default())
+        .collect();
+    let limit = team_match
+        .get_one::<String>("Limit")
+        .and_then(|limit| limit.parse().ok());
+    let team = Team::new(leader, helpers, limit, args.empty_pool);
+
+    if let Some(sim_match) = team_match.subcommand_matches("sim") {
+        let numhits: Option<u16> = sim_match
+            .get_one::<String>("Hits")
+            .and_then(|hits| hits.parse().ok());
+        plot_team_hits(&team, numhits);
+    } else {
+        let total = team.roll(args.critval, success, args.reroll, args.no_shitty_crits);
+        queue!(stdout(), Print(total)).ok();
+    }
+}
+
+fn run_stats(matches: &ArgMatches) {
+    let stats_match = matches.subcommand_matches("stats-gen").unwrap();
+    let arg = stats_match.get_one::<String>("Method").unwrap();
+    let Some(method) = Method::from(arg) else {
+        println!(
+            "\n{} '{}' is not a method. Write it as dice, what to drop and how many scores, like {}.\n",
+            "warning:".bold().dark_yellow(),
+            arg,
+            "4d6dl1x6".green()
+        );
+        return;
+    };
+    let min_total: u16 = stats_match
+        .get_one::<String>("Min Total")
+        .and_then(|total| total.parse().ok())
+        .unwrap_or(0);
+
+    if stats_match.subcommand_matches("sim").is_some() {
+        plot_stats(&method, min_total);
+    } else {
+        let (array, scores, rerolls) = method.roll(min_total);
+        queue!(stdout(), Print(array)).ok();
+        if rerolls > 0 {
+            println!(
+                "{}",
+                format!("Rerolled {} arrays under {}", rerolls, min_total).dark_yellow()
+            );
+        }
+        println!("{}", stats::get_array_line(&scores).bold());
+    }
+}
+
+fn run_deathsave(matches: &ArgMatches) {
+    let save_match = matches.subcommand_matches("deathsave").unwrap();
+    let mut state = State::load(&get_character(matches));
+    let mut saves = DeathSaves::load(&state);
+
+    if save_match.subcommand_matches("sim").is_some() {
+        show_death_saves(&saves);
+        return;
+    } else if save_match.subcommand_matches("reset").is_some() {
+        saves.reset();
+        println!("{}", saves);
+    } else {
+        let (save, outcome) = saves.roll();
+        queue!(stdout(), Print(save)).ok();
+        match outcome {
+            Some(Outcome::Revived) => println!("{}", "Back on their feet!".bold().green()),
+            Some(Outcome::Stable) => println!("{}", "Stabilized.".bold()),
+            Some(Outcome::Dead) => println!("{}", "Dead.".bold().dark_red()),
+            None => {}
+        }
+    }
+
+    saves.store(&mut state);
+    if let Err(e) = state.save() {
+        println!(
+            "{} could not save death saves: {}",
+            "warning:".bold().dark_yellow(),
+            e
+        );
+    }
+}
+
+fn run_attack(matches: &ArgMatches) {
+    let attack_match = matches.subcommand_matches("attack").unwrap();
+    let get_dice = |id: &str| {
+        DiceGroup::from(
+            attack_match.get_one::<String>(id).unwrap(),
+            0,
+            u16::MAX,
+            false,
+        )
+        .unwrap_or_default()
+    };
+    let get_number = |id: &str| -> Option<u16> {
+        attack_match
+            .get_one::<String>(id)
+            .and_then(|value| value.parse().ok())
+    };
+    let ac = get_number("AC").unwrap_or(10);
+    let attack = Attack::new(
+        get_dice("To Hit"),
+        get_dice("Damage"),
+        get_number("Attacks").unwrap_or(1),
+        get_number("Crit Range").unwrap_or(20),
+    );
+
+    if let Some(sim_match) = attack_match.subcommand_matches("sim") {
+        let (low, high) = sim_match
+            .get_one::<String>("AC Range")
+            .and_then(|range| range.split_once('-'))
+            .and_then(|(low, high)| Some((low.parse().ok()?, high.parse().ok()?)))
+            .unwrap_or((ac.saturating_sub(5), ac + 5));
+        plot_attack(&attack, ac, low, high);
+    } else {
+        let (round, damage) = attack.roll(ac);
+        queue!(stdout(), Print(round)).ok();
+        println!("Damage this round:\t{}", damage.to_string().bold());
+    }
+}
+
+fn run_percentile(matches: &ArgMatches) {
+    let percentile_match = matches.subcommand_matches("percentile").unwrap();
+    let get_number = |id: &str| -> u16 {
+        percentile_match
+            .get_one::<String>(id)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    };
+    let system = percentile_match
+        .get_one::<String>("System")
+        .and_then(|system| System::from(system))
+        .unwrap_or(System::Coc);
+    let test = Percentile::new(
+        system,
+        get_number("Skill"),
+        get_number("Bonus") as i16 - get_number("Penalty") as i16,
+    );
+
+    if percentile_match.subcommand_matches("sim").is_some() {
+        show_percentile(&test);
+    } else {
+        let (summary, _) = test.roll();
+        queue!(stdout(), Print(summary)).ok();
+    }
+}
+
+fn run_fate(matches: &ArgMatches) {
+    let fate_match = matches.subcommand_matches("fate").unwrap();
+    let get_number = |id: &str| -> Option<i16> {
+        fate_match
+            .get_one::<String>(id)
+            .and_then(|value| value.parse().ok())
+    };
+    let skill = get_number("Skill").unwrap_or(0);
+    let opposition = get_number("Opposition");
+
+    if fate_match.subcommand_matches("sim").is_some() {
+        show_fate(skill, opposition);
+    } else {
+        print!("{}", FateRoll::roll(skill, opposition));
+    }
+}
+
+fn run_ironsworn(matches: &ArgMatches) {
+    let ironsworn_match = matches.subcommand_matches("ironsworn").unwrap();
+    let mut state = State::load(&get_character(matches));
+    let mut momentum = Momentum::load(&state);
+    let get_number = |id: &str| -> u16 {
+        ironsworn_match
+            .get_one::<String>(id)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    };
+    let stat = get_number("Stat");
+    let adds = get_number("Adds");
+
+    if ironsworn_match.subcommand_matches("sim").is_some() {
+        show_ironsworn(stat + adds, &momentum);
+        return;
+    } else if let Some(oracle_match) = ironsworn_match.subcommand_matches("oracle") {
+        let threshold = oracle_match
+            .get_one::<String>("Odds")
+            .and_then(|odds| ironsworn::get_oracle_threshold(odds))
+            .unwrap_or(51);
+        queue!(stdout(), Print(ironsworn::ask_oracle(threshold))).ok();
+        return;
+    } else if let Some(momentum_match) = ironsworn_match.subcommand_matches("momentum") {
+        if momentum_match.get_flag("Reset") {
+            momentum.reset();
+        }
+        let delta: i16 = momentum_match
+            .get_one::<String>("Delta")
+            .and_then(|delta| delta.parse().ok())
+            .unwrap_or(0);
+        momentum.adjust(delta);
+        println!("{}", momentum);
+    } else {
+        let roll = ActionRoll::roll(stat, adds);
+        let summary = roll.resolve(&mut momentum, ironsworn_match.get_flag("Burn"));
+        queue!(stdout(), Print(summary)).ok();
+    }
+
+    momentum.store(&mut state);
+    if let Err(e) = state.save() {
+        println!(
+            "{} could not save momentum: {}",
+            "warning:".bold().dark_yellow(),
+            e
+        );
+    }
+}
+
+fn run_40k(matches: &ArgMatches) {
+    let sequence_match = matches.subcommand_matches("40k").unwrap();
+    let get_dice = |id: &str| {
+        sequence_match
+            .get_one::<String>(id)
+            .and_then(|value| wh40k::parse_characteristic(value))
+            .unwrap_or_default()
+    };
+    // 3+ and -2 read the same as 3 and 2
+    let get_number = |id: &str| -> Option<u16> {
+        sequence_match
+            .get_one::<String>(id)
+            .and_then(|value| value.trim_matches(['+', '-']).parse().ok())
+    };
+    let get_reroll = |id: &str| {
+        sequence_match
+            .get_one::<String>(id)
+            .and_then(|value| Reroll::from(value))
+    };
+    let sequence = Sequence {
+        attacks: get_dice("Attacks"),
+        skill: get_number("Skill").unwrap_or(4),
+        strength: get_number("Strength").unwrap_or(4),
+        toughness: get_number("Toughness").unwrap_or(4),
+        save: get_number("Save").unwrap_or(7),
+        ap: get_number("AP").unwrap_or(0),
+        invuln: get_number("Invuln"),
+        damage: get_dice("Damage"),
+        wounds: get_number("Wounds").unwrap_or(1).max(1),
+        models: get_number("Models"),
+        fnp: get_number("FNP"),
+        reroll_hits: get_reroll("Reroll Hits"),
+        reroll_wounds: get_reroll("Reroll Wounds"),
+    };
+
+    if sequence_match.subcommand_matches("sim").is_some() {
+        show_sequence(&sequence);
+    } else {
+        let (rolls, damage, slain) = sequence.roll();
+        queue!(stdout(), Print(rolls)).ok();
+        println!("Damage dealt:\t{}", damage.to_string().bold());
+        println!("Models slain:\t{}", slain.to_string().bold());
+    }
+}
+
+fn run_scatter(matches: &ArgMatches) {
+    let scatter_match = matches.subcommand_matches("scatter").unwrap();
+    let base = DiceGroup::from(
+        scatter_match.get_one::<String>("Base").unwrap(),
+        0,
+        u16::MAX,
+        false,
+    )
+    .unwrap_or_default();
+    let net_hits: u16 = scatter_match
+        .get_one::<String>("Net Hits")
+        .and_then(|hits| hits.parse().ok())
+        .unwrap_or(0);
+    let scatter = Scatter::new(base, net_hits);
+
+    if scatter_match.subcommand_matches("sim").is_some() {
+        plot_scatter(&scatter);
+    } else {
+        let (direction, rolled, landing) = scatter.roll();
+        let distance = rolled.saturating_sub(net_hits);
+        println!(
+            "Direction:\t{} ({})",
+            direction,
+            scatter::get_direction_label(direction)
+        );
+        println!(
+            "Distance:\t{} rolled - {} net hits = {} m",
+            rolled, net_hits, distance
+        );
+        if distance == 0 {
+            println!("{}", "Right on target".bold().green());
+        }
+        show_landing(&scatter, landing);
+    }
+}
+
+fn run_split(matches: &ArgMatches) {
+    let split_match = matches.subcommand_matches("split").unwrap();
+    let args: RollArgs = matches.into();
+    let success = if args.success == u16::MAX {
+        5
+    } else {
+        args.success
+    };
+    let mut dice = DiceGroup::from(
+        split_match.get_one::<String>("Dice").unwrap(),
+        0,
+        success,
+        args.no_shitty_crits,
+    )
+    .unwrap_or_default();
+    dice.apply_wounds(args.wounds);
+    dice.resolve_empty_pool(args.empty_pool);
+    let pool = dice.get_count();
+    let parts: Vec<u16> = split_match
+        .get_one::<String>("Into")
+        .map(|into| get_numbers(into))
+        .unwrap_or_default();
+
+    if let Some(sim_match) = split_match.subcommand_matches("sim") {
+        let targets = match sim_match.get_one::<String>("Targets") {
+            Some(targets) => get_numbers(targets),
+            None if !parts.is_empty() => vec![1; parts.len()],
+            None => vec![1, 1],
+        };
+        show_splits(&dice, &targets);
+    } else {
+        match Split::new(dice, parts) {
+            Some(split) => {
+                let total = split.roll(args.critval, success, args.reroll, args.no_shitty_crits);
+                queue!(stdout(), Print(total)).ok();
+            }
+            None => println!(
+                "{} the parts given with --into must add up to the whole pool of {} dice",
+                "warning:".bold().dark_yellow(),
+                pool
+            ),
+        }
+    }
+}
+
+// Reads a comma separated list like 6,6 and drops anything that isn't a number.
+fn get_numbers(list: &str) -> Vec<u16> {
+    list.split(',')
+        .filter_map(|number| number.trim().parse().ok())
+        .collect()
+}
+
+fn run_cast(matches: &ArgMatches) {
+    let cast_match = matches.subcommand_matches("cast").unwrap();
+    let args: RollArgs = matches.into();
+    // Shadowrun hits are fives and sixes unless told otherwise
+    let success = if args.success == u16::MAX {
+        5
+    } else {
+        args.success
+    };
+    let get_number = |id: &str| -> Option<u16> {
+        cast_match
+            .get_one::<String>(id)
+            .and_then(|value| value.parse().ok())
+    };
+    let spell = Spell {
+        pool: get_number("Pool").unwrap_or(0),
+        force: get_number("Force").unwrap_or(1),
+        magic: get_number("Magic").unwrap_or(1),
+        defense: get_number("Defense"),
+        drain_pool: get_number("Drain Pool").unwrap_or(0),
+        drain_modifier: cast_match
+            .get_one::<String>("Drain Modifier")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0),
+    };
+
+    if cast_match.subcommand_matches("sim").is_some() {
+        show_drain(&spell, success, args.wounds, args.empty_pool);
+    } else {
+        let chain = spell.cast(success, args.wounds, args.empty_pool);
+        queue!(stdout(), Print(chain)).ok();
+    }
+}
+
+fn run_matrix(matches: &ArgMatches) {
+    let matrix_match = matches.subcommand_matches("matrix").unwrap();
+    let args: RollArgs = matches.into();
+    let success = if args.success == u16::MAX {
+        5
+    } else {
+        args.success
+    };
+    let mut state = State::load(&get_character(matches));
+    let mut overwatch = Overwatch::load(&state);
+
+    if matrix_match.get_flag("Reset") {
+        flair::print_matrix_break(&overwatch.link, "Jacked Out");
+        overwatch.reset();
+    } else {
+        flair::print_matrix_link(&overwatch.link);
+        let attack = matrix_match
+            .get_one::<String>("Attack")
+            .and_then(|attack| attack.parse::<u16>().ok());
+        if let Some(attack) = attack {
+            let action = matrix_match.get_one::<String>("Action").unwrap();
+            let defense: u16 = matrix_match
+                .get_one::<String>("Defense")
+                .and_then(|defense| defense.parse().ok())
+                .unwrap_or(0);
+            let (rolls, hits) = matrix::act(
+                action,
+                attack,
+                defense,
+                success,
+                args.wounds,
+                args.empty_pool,
+            );
+            queue!(stdout(), Print(rolls)).ok();
+            let converged = overwatch.add(hits);
+            flair::print_overwatch(overwatch.score, matrix::CONVERGENCE);
+            if converged {
+                println!(
+                    "{}",
+                    "CONVERGENCE. GOD has found you: your deck is bricked and your location is traced."
+                        .bold()
+                        .dark_red()
+                );
+                flair::print_matrix_break(&overwatch.link, "Convergence");
+                overwatch.reset();
+            }
+        } else {
+            flair::print_overwatch(overwatch.score, matrix::CONVERGENCE);
+        }
+    }
+
+    overwatch.store(&mut state);
+    if let Err(e) = state.save() {
+        println!(
+            "{} could not save Overwatch Score: {}",
+            "warning:".bold().dark_yellow(),
+            e
+        );
+    }
+}
+
+fn run_damage(matches: &ArgMatches) {
+    let damage_match = matches.subcommand_matches("damage").unwrap();
+    let mut state = State::load(&get_character(matches));
+    let mut monitor = ConditionMonitor::load(&state);
+    if let Some(body) = damage_match.get_one::<String>("Body") {
+        monitor.set_body(body.parse().unwrap_or(0));
+    }
+    if let Some(willpower) = damage_match.get_one::<String>("Willpower") {
+        monitor.set_willpower(willpower.parse().unwrap_or(0));
+    }
+    if let Some(take) = damage_match.subcommand_matches("take") {
+        let arg = take.get_one::<String>("Damage").unwrap();
+        match Damage::from(arg) {
+            Some(damage) => monitor.take(damage),
+            None => show_damage_warning(arg),
+        }
+    } else if let Some(heal) = damage_match.subcommand_matches("heal") {
+        match heal.get_one::<String>("Damage") {
+            Some(arg) => match Damage::from(arg) {
+                Some(damage) => monitor.heal(Some(damage)),
+                None => show_damage_warning(arg),
+            },
+            None => monitor.heal(None),
+        }
+    }
+    monitor.store(&mut state);
+    if let Err(e) = state.save() {
+        println!(
+            "{} could not save condition monitor: {}",
+            "warning:".bold().dark_yellow(),
+            e
+        );
+    }
+    println!("\n{}", state.get_character().bold().underlined());
+    print!("{}", monitor);
+}
+
 fn run_demo() {
     let mut previous = Summary::new();
     let mut total = Summary::new();
@@ -233,10 +975,7 @@ fn run_demo() {
     roll(
         "5d6",
         &mut Summary::new(),
-        u16::MAX,
-        false,
-        u16::MAX,
-        u16::MAX,
+        &RollArgs::with_success(u16::MAX),
         &mut total,
     );
 
@@ -250,10 +989,7 @@ The modifier may be applied to multiple dice using the multiplication operator.
     roll(
         "2*1d20+8",
         &mut Summary::new(),
-        20,
-        false,
-        u16::MAX,
-        u16::MAX,
+        &RollArgs::with_success(20),
         &mut total,
     );
     queue!(stdout(), Print(total)).ok();
@@ -268,48 +1004,157 @@ The dice sequence \"2*1d20+8 x*1d8+4\" rolls a d8 dice with a +4 modifier for ev
     roll(
         "3*1d20+8",
         &mut previous,
-        14,
-        false,
-        u16::MAX,
-        u16::MAX,
+        &RollArgs::with_success(14),
         &mut total,
     );
     roll(
         "x*1d8+4",
         &mut previous,
-        14,
-        false,
-        u16::MAX,
-        u16::MAX,
+        &RollArgs::with_success(14),
         &mut total,
     );
     queue!(stdout(), Print(total)).ok();
 }
 
-fn roll(
-    dice: &str,
-    previous: &mut Summary,
-    success: u16,
-    no_shitty_crits: bool,
-    critval: u16,
-    reroll: u16,
-    total: &mut Summary,
-) {
-    let d = DiceGroup::from_previous(
+fn roll(dice: &str, previous: &mut Summary, args: &RollArgs, total: &mut Summary) {
+    let mut d = DiceGroup::from_previous(
         dice,
         previous.hits,
         previous.crits,
-        success,
-        no_shitty_crits,
+        args.success,
+        args.no_shitty_crits,
     )
     .unwrap_or_default();
-    let mut roller = Roller::from_dice_group(d, critval, success, reroll);
-    roller.roll(no_shitty_crits);
-    let summary = roller.get_summary();
+    d.apply_wounds(args.wounds);
+    d.resolve_empty_pool(args.empty_pool);
+    let sides = d.get_sides().unwrap_or(6);
+    let wild = d.is_wild();
+    let roll_keep = d.get_roll_keep().is_some();
+    let yze = d.get_yze().is_some();
+    let symbols = !d.get_symbol_dice().is_empty();
+    let hit_pool = d.is_hit_pool();
+    let mut roller = Roller::from_dice_group(d, args.critval, args.success, args.reroll);
+    roller.roll(args.no_shitty_crits);
+    let mut summary = roller.get_summary();
+    summary.set_rules(args.rules);
+    // Edge boosts are a 6e rule for pools of d6s counting hits
+    if args.rules == Rules::Sr6 && hit_pool {
+        for boost in &args.boosts {
+            boost.apply(&mut summary, args.edge, sides, args.success);
+        }
+    } else if !args.boosts.is_empty() {
+        show_boost_warning(dice);
+    }
+    if wild {
+        summary.check_raises(args.dc.unwrap_or(4));
+    } else if let (Some(tn), true) = (args.dc, roll_keep) {
+        summary.check_called_raises(tn, args.raises);
+    } else if let (Some(dc), 20) = (args.dc, sides) {
+        summary.check_dc(dc);
+    }
+    if let Some(bands) = &args.bands {
+        summary.check_bands(bands);
+    }
+    if let (Rules::Blades, 6) = (args.rules, sides) {
+        summary.check_action();
+    }
+    if yze {
+        if args.push {
+            summary.push();
+        }
+        summary.check_yze();
+    }
+    if symbols {
+        summary.check_symbols();
+    }
     *total += summary.to_owned();
     *previous = summary;
 }
 
+fn get_character(matches: &ArgMatches) -> String {
+    matches
+        .get_one::<String>("Character")
+        .cloned()
+        .or_else(|| std::env::var("ROLL_CHARACTER").ok())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+fn get_wounds(matches: &ArgMatches) -> u16 {
+    ConditionMonitor::load(&State::load(&get_character(matches))).get_wound_modifier()
+}
+
+fn get_rules(matches: &ArgMatches) -> Rules {
+    matches
+        .get_one::<String>("Rules")
+        .and_then(|rules| Rules::from(rules))
+        .unwrap_or(Rules::Sr5)
+}
+
+fn get_edge(matches: &ArgMatches) -> u16 {
+    matches
+        .get_one::<String>("Edge")
+        .and_then(|edge| edge.parse().ok())
+        .unwrap_or(0)
+}
+
+fn get_dc(matches: &ArgMatches) -> Option<u16> {
+    matches
+        .get_one::<String>("DC")
+        .and_then(|dc| dc.parse().ok())
+}
+
+fn get_raises(matches: &ArgMatches) -> u16 {
+    matches
+        .get_one::<String>("Raises")
+        .and_then(|raises| raises.parse().ok())
+        .unwrap_or(0)
+}
+
+fn get_bands(matches: &ArgMatches) -> Option<Bands> {
+    let arg = matches.get_one::<String>("Bands")?;
+    let bands = Bands::from(arg);
+    if bands.is_none() {
+        println!(
+            "\n{} '{}' are not bands. Give a preset (pbta, aw, dw, motw) or the highest total of each band, like {}.\n",
+            "warning:".bold().dark_yellow(),
+            arg,
+            "6,9".green()
+        );
+    }
+    bands
+}
+
+// Blades rolls two dice and keeps the lowest unless another rule is asked for.
+fn get_empty_pool(matches: &ArgMatches) -> EmptyPool {
+    if get_rules(matches) == Rules::Blades
+        && matches.value_source("Empty Pool") != Some(ValueSource::CommandLine)
+    {
+        return EmptyPool::LowestOfTwo;
+    }
+    matches
+        .get_one::<String>("Empty Pool")
+        .and_then(|rule| EmptyPool::from(rule))
+        .unwrap_or(EmptyPool::AutoFail)
+}
+
+fn show_damage_warning(arg: &str) {
+    println!(
+        "\n{} '{}' is not a damage code. Use a number followed by P or S, like {}.\n",
+        "warning:".bold().dark_yellow(),
+        arg,
+        "4P".green()
+    )
+}
+
+fn show_boost_warning(dice: &str) {
+    println!(
+        "\n{} --boost was ignored for '{}'. Edge boosts need {} and a pool of d6s counting hits.\n",
+        "warning:".bold().dark_yellow(),
+        dice,
+        "--rules sr6".green()
+    )
+}
+
 fn show_dice_warning() {
     println!("\n{} The following suggested arguments were not provided:\n\t{}\n\nThe dice roller has no dice to roll...\n", 
         "warning:".bold().dark_yellow(),
@@ -359,6 +1204,71 @@ fn get_matches() -> ArgMatches {
                 .long("no-shitty-crits")
                 .help("Change crit behavior to be in line with the popular nsc homebrew rules.")
                 .action(ArgAction::SetTrue)
+        ).arg(
+            Arg::new("Rules")
+                .short('m')
+                .long("rules")
+                .value_parser(["sr5", "sr6", "pf2e", "blades"])
+                .default_value("sr5")
+                .help("The rules used to read the roll. Under pf2e, --dc reports the four degrees of success. Under blades, a d6 pool is read from its highest die.")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("Attack Rating")
+                .long("ar")
+                .help("(sr6) The attacker's Attack Rating. Compared with --dr to award Edge.")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("Defense Rating")
+                .long("dr")
+                .help("(sr6) The defender's Defense Rating. Compared with --ar to award Edge.")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("Boost")
+                .long("boost")
+                .value_parser(["plus-one", "reroll-one", "push"])
+                .help("(sr6) Spend Edge after the roll: +1 to a die, reroll one die, or add Edge dice with exploding sixes. May be given more than once.")
+                .action(ArgAction::Append)
+        ).arg(
+            Arg::new("Edge")
+                .short('e')
+                .long("edge")
+                .help("(sr6) The Edge dice added by a push boost.")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("DC")
+                .long("dc")
+                .visible_alias("tn")
+                .help("The DC (or target number) a d20 roll has to meet. Reports success or failure, natural 20s and 1s, and the margin.")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("Bands")
+                .long("bands")
+                .help("Reads each total as an outcome band: the highest total of every band but the last, like 6,9, or a preset (pbta, aw, dw, motw).")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("Push")
+                .long("push")
+                .help("(year zero) Push the roll, rerolling every die that isn't a six or a one on a base or gear die.")
+                .action(ArgAction::SetTrue)
+        ).arg(
+            Arg::new("Raises")
+                .long("raises")
+                .help("(roll-and-keep) Raises called before the roll, each adding 5 to the TN.")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("Empty Pool")
+                .long("empty-pool")
+                .value_parser(["chance", "fail", "one", "lowest"])
+                .default_value("fail")
+                .help("What happens when modifiers leave no dice: roll a chance die that only hits on its highest face, fail automatically, roll one die anyway, or roll two and keep the lowest (the default under blades).")
+                .action(ArgAction::Set)
+        ).arg(
+            Arg::new("Character")
+                .short('C')
+                .long("character")
+                .global(true)
+                .help("The character whose persisted state (condition monitor, etc.) applies to this roll. Defaults to $ROLL_CHARACTER or 'default'.")
+                .action(ArgAction::Set)
         ).subcommand(
             Command::new("help-dice")
                 .about("Show more information on dice syntax and behavior.")
@@ -372,6 +1282,439 @@ fn get_matches() -> ArgMatches {
                 .about(format!("Simulate and predict probabilities of possible outcomes for success totals. This is an alias for {}.", "sim -bts [TARGET TOTAL]".dark_cyan()))
                 .arg(
                     Arg::new("Target Total").action(ArgAction::Set))
+        ).subcommand(
+            Command::new("pool")
+                .about("Roll a pool built from itemized modifiers and show the ledger above the roll.")
+                .arg(
+                    Arg::new("Pool")
+                        .required(true)
+                        .help("The pool items in 'name:dice' format joined with + and -, like \"agility:5 + pistols:6 - recoil:1\".")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Sides")
+                        .long("sides")
+                        .help("The number of sides on each die in the pool. Defaults to 6.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Bonus")
+                        .short('b')
+                        .long("bonus")
+                        .allow_negative_numbers(true)
+                        .help("A modifier added to the total of the roll, like 2 or -2. It never changes the number of dice.")
+                        .action(ArgAction::Set)
+                )
+        ).subcommand(
+            Command::new("team")
+                .about("Roll a teamwork test. Every helper's hits add dice to the leader's pool and raise the leader's limit.")
+                .arg(
+                    Arg::new("Leader")
+                        .long("leader")
+                        .required(true)
+                        .help("The leader's dice, like 10d6.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Helper")
+                        .long("helper")
+                        .help("A helper's dice, like 6d6. May be given once per helper.")
+                        .action(ArgAction::Append)
+                ).arg(
+                    Arg::new("Limit")
+                        .short('l')
+                        .long("limit")
+                        .help("The leader's limit before helpers raise it.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the exact distribution of the leader's hits for the whole team.")
+                        .arg(
+                            Arg::new("Hits")
+                                .short('n')
+                                .long("nhits")
+                                .help("Set the number of hits that would count as a success for the team.")
+                                .action(ArgAction::Set)
+                        )
+                )
+        ).subcommand(
+            Command::new("stats-gen")
+                .about("Roll an array of ability scores, rerolling the whole array if its total is too low.")
+                .arg(
+                    Arg::new("Method")
+                        .long("method")
+                        .default_value("4d6dl1x6")
+                        .help("How each score is rolled and how many there are, like 4d6dl1x6 (4d6 drop lowest, six times) or 3d6x6.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Min Total")
+                        .long("min-total")
+                        .help("Reroll the whole array if its scores add up to less than this.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the exact distribution of a single score and of the point buy the whole array is worth.")
+                )
+        ).subcommand(
+            Command::new("deathsave")
+                .about("Roll a death saving throw for a dying character. The tally of successes and failures is kept between rolls.")
+                .subcommand(
+                    Command::new("sim")
+                        .about("Show the exact odds of reviving, stabilizing or dying from the current tally.")
+                ).subcommand(
+                    Command::new("reset")
+                        .about("Clear the tally, like after the character is healed.")
+                )
+        ).subcommand(
+            Command::new("percentile")
+                .about("Roll a d100 under a skill, read the way a percentile system does: Call of Cthulhu, Warhammer Fantasy or Mothership.")
+                .arg(
+                    Arg::new("Skill")
+                        .long("skill")
+                        .required(true)
+                        .help("The skill or stat the roll has to get under.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("System")
+                        .long("system")
+                        .value_parser(["coc", "wfrp", "mothership"])
+                        .default_value("coc")
+                        .help("How to read the roll: coc for regular, hard and extreme successes, wfrp for Success Levels, mothership for crits on doubles.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Bonus")
+                        .long("bonus")
+                        .help("Bonus dice (coc, up to 2) or advantage (mothership). Cancels against --penalty.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Penalty")
+                        .long("penalty")
+                        .help("Penalty dice (coc, up to 2) or disadvantage (mothership). Cancels against --bonus.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the exact chance of reaching each level of success.")
+                )
+        ).subcommand(
+            Command::new("fate")
+                .about("Roll 4dF plus a skill and read it off the Fate ladder, from Terrible (-2) to Legendary (+8).")
+                .arg(
+                    Arg::new("Skill")
+                        .long("skill")
+                        .allow_negative_numbers(true)
+                        .help("The skill rating added to the dice, like 3 for Good.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Opposition")
+                        .long("opposition")
+                        .allow_negative_numbers(true)
+                        .help("The passive opposition or difficulty. Reports the shifts and whether the roll fails, ties, succeeds or succeeds with style.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the exact chance of every result on the ladder, and of each outcome against --opposition.")
+                )
+        ).subcommand(
+            Command::new("ironsworn")
+                .about("Make an Ironsworn action roll: a d6 plus a stat and adds against two d10 challenge dice. Momentum is kept between rolls.")
+                .arg(
+                    Arg::new("Stat")
+                        .long("stat")
+                        .help("The stat added to the action die, like 3 for Edge.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Adds")
+                        .long("adds")
+                        .help("Any adds from assets or the move.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Burn")
+                        .long("burn")
+                        .help("Burn momentum in place of the action score if it would do better, then reset momentum to +2.")
+                        .action(ArgAction::SetTrue)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the exact chance of a strong hit, weak hit or miss for every stat plus adds from 0 to 10.")
+                ).subcommand(
+                    Command::new("momentum")
+                        .about("Show the momentum track, or move it up or down.")
+                        .arg(
+                            Arg::new("Delta")
+                                .allow_negative_numbers(true)
+                                .help("How much to gain or lose, like 2 or -1.")
+                                .action(ArgAction::Set)
+                        ).arg(
+                            Arg::new("Reset")
+                                .long("reset")
+                                .help("Put momentum back to +2 first.")
+                                .action(ArgAction::SetTrue)
+                        )
+                ).subcommand(
+                    Command::new("oracle")
+                        .about("Ask the oracle a yes or no question on a d100. Doubles are an extreme result or a twist.")
+                        .arg(
+                            Arg::new("Odds")
+                                .long("odds")
+                                .value_parser(["almost-certain", "likely", "fifty-fifty", "unlikely", "small-chance"])
+                                .default_value("fifty-fifty")
+                                .help("How likely the answer is to be yes.")
+                                .action(ArgAction::Set)
+                        )
+                )
+        ).subcommand(
+            Command::new("40k")
+                .about("Roll a Warhammer 40,000 attack sequence: hit rolls, wound rolls, saving throws and damage, each rolling only what got through the one before.")
+                .arg(
+                    Arg::new("Attacks")
+                        .long("attacks")
+                        .required(true)
+                        .help("The number of attacks, like 10 or 2D6.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Skill")
+                        .long("bs")
+                        .required(true)
+                        .help("The Ballistic or Weapon Skill each hit roll needs, like 3 for 3+.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Strength")
+                        .long("strength")
+                        .required(true)
+                        .help("The attack's Strength, compared with --toughness for the wound roll.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Toughness")
+                        .long("toughness")
+                        .required(true)
+                        .help("The target's Toughness.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Save")
+                        .long("save")
+                        .help("The target's armour save, like 3 for 3+. No save if left out.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("AP")
+                        .long("ap")
+                        .allow_hyphen_values(true)
+                        .help("The attack's Armour Penetration, like 2 or -2. Worsens the armour save but not an invulnerable one.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Invuln")
+                        .long("invuln")
+                        .help("The target's invulnerable save, used when it beats the armour save after AP.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Damage")
+                        .long("damage")
+                        .default_value("1")
+                        .help("The damage of each unsaved wound, like 2 or D3+1.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Wounds")
+                        .long("wounds")
+                        .help("The wounds on each model in the target unit. Damage left over when a model is slain is lost.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Models")
+                        .long("models")
+                        .help("The number of models in the target unit. The sequence stops once they are all slain.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("FNP")
+                        .long("fnp")
+                        .help("The target's feel no pain, like 5 for 5+, rolled against every point of damage.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Reroll Hits")
+                        .long("reroll-hits")
+                        .value_parser(["ones", "fails"])
+                        .help("Reroll hit rolls of 1, or every failed hit roll.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Reroll Wounds")
+                        .long("reroll-wounds")
+                        .value_parser(["ones", "fails"])
+                        .help("Reroll wound rolls of 1, or every failed wound roll.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the exact distribution of unsaved wounds and of models slain, with the chance of getting through each stage.")
+                )
+        ).subcommand(
+            Command::new("attack")
+                .about("Roll a round of d20 attacks against an Armor Class, then damage for every hit. Critical hits double the damage dice.")
+                .arg(
+                    Arg::new("To Hit")
+                        .long("to-hit")
+                        .required(true)
+                        .help("The attack roll, like 1d20+8 or 1d20adv+5.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("AC")
+                        .long("ac")
+                        .required(true)
+                        .help("The target's Armor Class.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Damage")
+                        .long("damage")
+                        .required(true)
+                        .help("The damage roll for a hit, like 1d8+4.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Attacks")
+                        .short('n')
+                        .long("attacks")
+                        .help("The number of attacks in the round.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Crit Range")
+                        .long("crit-range")
+                        .help("The lowest natural roll that is a critical hit, like 19 for an expanded crit range.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the exact damage distribution for the round and the expected damage across a range of Armor Classes.")
+                        .arg(
+                            Arg::new("AC Range")
+                                .long("ac-range")
+                                .help("The Armor Classes to compare, like 12-22. Defaults to 5 either side of --ac.")
+                                .action(ArgAction::Set)
+                        )
+                )
+        ).subcommand(
+            Command::new("scatter")
+                .about("Scatter a grenade or other area weapon and show where it lands.")
+                .arg(
+                    Arg::new("Base")
+                        .long("base")
+                        .default_value("1d6")
+                        .help("The weapon's base scatter in metres, like 1d6 for a standard grenade.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Net Hits")
+                        .short('n')
+                        .long("net-hits")
+                        .help("Net hits on the attack, each one bringing the grenade a metre closer.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show a heatmap of where the grenade lands and the chance of each distance.")
+                )
+        ).subcommand(
+            Command::new("split")
+                .about("Split a pool across several targets and roll each part on its own.")
+                .arg(
+                    Arg::new("Dice")
+                        .required(true)
+                        .help("The whole pool, like 12d6.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Into")
+                        .long("into")
+                        .help("How many dice go to each target, like 6,6 or 8,4. The parts must add up to the pool.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Compare every way to split the pool by the chance of reaching each target.")
+                        .arg(
+                            Arg::new("Targets")
+                                .short('t')
+                                .long("targets")
+                                .help("The hits wanted on each target, like 2,3. Defaults to one hit for each part of --into.")
+                                .action(ArgAction::Set)
+                        )
+                )
+        ).subcommand(
+            Command::new("cast")
+                .about("Cast a spell: roll spellcasting limited by Force, the target's defense, then drain resistance.")
+                .arg(
+                    Arg::new("Pool")
+                        .short('p')
+                        .long("pool")
+                        .required(true)
+                        .help("The number of dice in the spellcasting pool.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Force")
+                        .short('f')
+                        .long("force")
+                        .required(true)
+                        .help("The Force of the spell. Spellcasting hits are limited by Force.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Magic")
+                        .long("magic")
+                        .required(true)
+                        .help("The caster's Magic. Drain is physical when the spellcasting hits exceed it.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Defense")
+                        .long("defense")
+                        .help("The number of dice the target rolls to resist the spell.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Drain Pool")
+                        .long("drain-pool")
+                        .required(true)
+                        .help("The number of dice rolled to resist drain.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Drain Modifier")
+                        .long("drain-modifier")
+                        .allow_negative_numbers(true)
+                        .help("The spell's drain modifier, added to the Force to find the drain value (minimum 2).")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("sim")
+                        .about("Show the chance of success and of taking drain at every Force up to twice the caster's Magic.")
+                )
+        ).subcommand(
+            Command::new("matrix")
+                .about("Roll an illegal Matrix action. The defender's hits build the persisted Overwatch Score until convergence at 40.")
+                .arg(
+                    Arg::new("Action")
+                        .default_value("Matrix action")
+                        .help("The name of the action, like 'Hack on the Fly'.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Attack")
+                        .short('a')
+                        .long("attack")
+                        .help("The number of dice in the decker's pool. Without it the current Overwatch Score is shown.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Defense")
+                        .short('d')
+                        .long("defense")
+                        .help("The number of dice in the defending pool.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Reset")
+                        .long("reset")
+                        .help("Jack out, dropping the link and the Overwatch Score.")
+                        .action(ArgAction::SetTrue)
+                )
+        ).subcommand(
+            Command::new("damage")
+                .about("Track a character's condition monitor. Wound modifiers are removed from every pool of d6s counting hits that character rolls.")
+                .arg(
+                    Arg::new("Body")
+                        .long("body")
+                        .help("Set the physical condition monitor size from the character's Body.")
+                        .action(ArgAction::Set)
+                ).arg(
+                    Arg::new("Willpower")
+                        .long("willpower")
+                        .help("Set the stun condition monitor size from the character's Willpower.")
+                        .action(ArgAction::Set)
+                ).subcommand(
+                    Command::new("take")
+                        .about("Mark damage on the condition monitor, like 4P or 6S.")
+                        .arg(Arg::new("Damage").required(true).action(ArgAction::Set))
+                ).subcommand(
+                    Command::new("heal")
+                        .about("Heal damage from the condition monitor, like 2P. Heals everything if no damage is given.")
+                        .arg(Arg::new("Damage").action(ArgAction::Set))
+                )
         ).subcommand(
             Command::new("sim")
                 .about("Simulate and predict probabilities of possible outcomes.")
@@ -0,0 +1,100 @@
+use crate::{
+    flair,
+    roll::{DiceGroup, EmptyPool, Roller, Summary},
+    state::State,
+};
+
+// GOD converges on a decker once their Overwatch Score reaches this.
+pub const CONVERGENCE: u16 = 40;
+
+// A decker's running Overwatch Score and the QEH link it was built up on.
+pub struct Overwatch {
+    pub score: u16,
+    pub link: Vec<u16>,
+}
+
+impl Overwatch {
+    pub fn load(state: &State) -> Self {
+        let link: Option<Vec<u16>> = (0..4).map(|i| state.get(&format!("link{}", i))).collect();
+        Self {
+            score: state.get("overwatch").unwrap_or(0),
+            link: link.unwrap_or_else(flair::get_matrix_link),
+        }
+    }
+
+    pub fn store(&self, state: &mut State) {
+        state.set("overwatch", self.score);
+        for (i, addr) in self.link.iter().enumerate() {
+            state.set(&format!("link{}", i), *addr);
+        }
+    }
+
+    // Adds the opposition's hits, returning true when that brings on convergence.
+    pub fn add(&mut self, hits: u16) -> bool {
+        self.score += hits;
+        self.score >= CONVERGENCE
+    }
+
+    // Drops the link; the next action starts a fresh session.
+    pub fn reset(&mut self) {
+        self.score = 0;
+        self.link = flair::get_matrix_link();
+    }
+}
+
+// Rolls an illegal action against its defense, returning both rolls and the defender's hits.
+// Only the decker's own pool takes their wounds.
+pub fn act(
+    action: &str,
+    attack: u16,
+    defense: u16,
+    success: u16,
+    wounds: u16,
+    empty_pool: EmptyPool,
+) -> (Summary, u16) {
+    let mut rolls = Summary::new();
+    let mut dice = get_dice(attack, success);
+    dice.apply_wounds(wounds);
+    dice.resolve_empty_pool(empty_pool);
+    let mut attacker = Roller::roll_group(dice, success);
+    attacker.set_label(action);
+    let mut defender = Roller::roll_group(get_dice(defense, success), success);
+    defender.set_label("Defense");
+    let hits = defender.hits;
+    defender.add_note(format!("Overwatch Score +{}", hits));
+    rolls += attacker;
+    rolls += defender;
+    (rolls, hits)
+}
+
+fn get_dice(count: u16, success: u16) -> DiceGroup {
+    DiceGroup::from(&format!("{}d6", count), 0, success, false).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overwatch() {
+        let mut overwatch = Overwatch {
+            score: 0,
+            link: vec![0; 4],
+        };
+        assert!(!overwatch.add(CONVERGENCE - 1));
+        assert!(overwatch.add(1));
+        overwatch.reset();
+        assert_eq!(overwatch.score, 0);
+        assert_eq!(overwatch.link.len(), 4);
+
+        // the defender's hits feed the score and can never outnumber its dice
+        let (rolls, hits) = act("Hack on the Fly", 6, 4, 5, 0, EmptyPool::AutoFail);
+        assert!(hits <= 4);
+        assert!(rolls
+            .to_string()
+            .contains(&format!("Overwatch Score +{}", hits)));
+        // a decker wounded out of every die fails outright
+        let (rolls, _) = act("Hack on the Fly", 2, 4, 5, 2, EmptyPool::AutoFail);
+        assert!(rolls.to_string().contains("Automatic failure"));
+    }
+}
@@ -0,0 +1,332 @@
+use std::{collections::HashMap, io::Stdout};
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+
+use crate::{
+    components::Component,
+    roll::{Result, Summary},
+};
+
+// A d100 roll-under system and how it reads the roll.
+#[derive(Clone, Copy, PartialEq)]
+pub enum System {
+    // Call of Cthulhu 7e: regular, hard (1/2) and extreme (1/5) successes, with bonus and
+    // penalty tens dice
+    Coc,
+    // Warhammer Fantasy 4e: Success Levels from the difference in tens digits
+    Wfrp,
+    // Mothership 1e: advantage and disadvantage, and doubles crit
+    Mothership,
+}
+
+impl System {
+    pub fn from(arg: &str) -> Option<Self> {
+        match arg {
+            "coc" => Some(Self::Coc),
+            "wfrp" => Some(Self::Wfrp),
+            "mothership" => Some(Self::Mothership),
+            _ => None,
+        }
+    }
+
+    // Every level the roll can reach, best first.
+    pub fn get_levels(&self) -> &'static [&'static str] {
+        match self {
+            Self::Coc => &[
+                "Critical success",
+                "Extreme success",
+                "Hard success",
+                "Regular success",
+                "Failure",
+                "Fumble",
+            ],
+            Self::Wfrp => &[
+                "Astounding success",
+                "Impressive success",
+                "Success",
+                "Marginal success",
+                "Marginal failure",
+                "Failure",
+                "Impressive failure",
+                "Astounding failure",
+            ],
+            Self::Mothership => &["Critical success", "Success", "Failure", "Critical failure"],
+        }
+    }
+
+    // How many of the levels are successes.
+    pub fn get_successes(&self) -> usize {
+        match self {
+            Self::Mothership => 2,
+            _ => 4,
+        }
+    }
+
+    // Bonus and penalty dice cancel out, and each system caps how many are left.
+    fn get_max_bonus(&self) -> i16 {
+        match self {
+            Self::Coc => 2,
+            Self::Wfrp => 0,
+            Self::Mothership => 1,
+        }
+    }
+}
+
+// A roll-under test against a skill. A positive bonus is bonus dice or advantage, a negative
+// one penalty dice or disadvantage.
+pub struct Percentile {
+    system: System,
+    skill: u16,
+    bonus: i16,
+}
+
+impl Percentile {
+    pub fn new(system: System, skill: u16, bonus: i16) -> Self {
+        let max = system.get_max_bonus();
+        Self {
+            system,
+            skill,
+            bonus: bonus.clamp(-max, max),
+        }
+    }
+
+    pub fn get_system(&self) -> System {
+        self.system
+    }
+
+    // The level a roll reaches, as an index into the system's levels.
+    pub fn get_level(&self, roll: u16) -> usize {
+        let skill = self.skill;
+        match self.system {
+            System::Coc => match roll {
+                _ if self.is_coc_fumble(roll) => 5,
+                1 => 0,
+                _ if roll <= skill / 5 => 1,
+                _ if roll <= skill / 2 => 2,
+                _ if roll <= skill => 3,
+                _ => 4,
+            },
+            System::Wfrp => {
+                let sl = self.get_success_levels(roll);
+                match (self.is_wfrp_success(roll), sl) {
+                    (true, 6..) => 0,
+                    (true, 4..=5) => 1,
+                    (true, 2..=3) => 2,
+                    (true, _) => 3,
+                    (false, -1..) => 4,
+                    (false, -3..=-2) => 5,
+                    (false, -5..=-4) => 6,
+                    (false, _) => 7,
+                }
+            }
+            // 90-99 always fails
+            System::Mothership => match (roll < skill && roll < 90, roll / 10 == roll % 10) {
+                (true, true) => 0,
+                (true, false) => 1,
+                (false, false) => 2,
+                (false, true) => 3,
+            },
+        }
+    }
+
+    // WFRP: 01-05 always succeeds and 96-00 always fails.
+    fn is_wfrp_success(&self, roll: u16) -> bool {
+        roll <= 5 || (roll <= self.skill && roll < 96)
+    }
+
+    // WFRP: the skill's tens digit less the roll's, never against the way the test went.
+    pub fn get_success_levels(&self, roll: u16) -> i16 {
+        let sl = (self.skill / 10) as i16 - (roll / 10) as i16;
+        if self.is_wfrp_success(roll) {
+            sl.max(0)
+        } else {
+            sl.min(0)
+        }
+    }
+
+    // CoC: a 00 always fumbles, and so does 96 or more when the skill is under 50.
+    fn is_coc_fumble(&self, roll: u16) -> bool {
+        roll == 100 || (self.skill < 50 && roll >= 96)
+    }
+
+    // One units die and a tens die for every d100, plus one for each bonus or penalty die.
+    // Mothership rolls whole d100s for advantage instead.
+    fn get_digits(&self) -> usize {
+        let extra = self.bonus.unsigned_abs() as usize;
+        match self.system {
+            System::Mothership => 2 * (1 + extra),
+            _ => 2 + extra,
+        }
+    }
+
+    // Every d100 the digits make. CoC and WFRP read a 00 as 100, Mothership as 0.
+    fn get_candidates(&self, digits: &[u16]) -> Vec<u16> {
+        match self.system {
+            System::Mothership => digits
+                .chunks(2)
+                .map(|pair| pair[0] * 10 + pair[1])
+                .collect(),
+            _ => digits[1..]
+                .iter()
+                .map(|tens| match tens * 10 + digits[0] {
+                    0 => 100,
+                    roll => roll,
+                })
+                .collect(),
+        }
+    }
+
+    // The best roll with a bonus, the worst with a penalty.
+    fn pick(&self, candidates: &[u16]) -> u16 {
+        let key = |roll: &&u16| (self.get_level(**roll), **roll);
+        let picked = if self.bonus >= 0 {
+            candidates.iter().min_by_key(key)
+        } else {
+            candidates.iter().max_by_key(key)
+        };
+        *picked.unwrap_or(&100)
+    }
+
+    pub fn roll(&self) -> (Summary, u16) {
+        let digits: Vec<u16> = (0..self.get_digits())
+            .map(|_| rand::random::<u16>() % 10)
+            .collect();
+        let candidates = self.get_candidates(&digits);
+        let roll = self.pick(&candidates);
+        let mut summary = Summary::new();
+        summary.hide_hits();
+        let mut kept = false;
+        for candidate in candidates {
+            let dropped = kept || candidate != roll;
+            kept |= !dropped;
+            summary.add_result(Result {
+                dropped,
+                ..Result::new(candidate, 100)
+            });
+        }
+        let level = self.system.get_levels()[self.get_level(roll)];
+        summary.add_note(match self.system {
+            System::Wfrp => format!(
+                "{} ({:+} SL) against {}",
+                level,
+                self.get_success_levels(roll),
+                self.skill
+            ),
+            _ => format!("{} against {}", level, self.skill),
+        });
+        (summary, roll)
+    }
+
+    // The exact chance of each level, counting every way the digits can fall.
+    pub fn get_chances(&self) -> Vec<f64> {
+        let digits = self.get_digits();
+        let combinations = 10_u32.pow(digits as u32);
+        let p = 1. / combinations as f64;
+        let mut chances = vec![0.; self.system.get_levels().len()];
+        for index in 0..combinations {
+            let rolled: Vec<u16> = (0..digits)
+                .map(|i| (index / 10_u32.pow(i as u32) % 10) as u16)
+                .collect();
+            let roll = self.pick(&self.get_candidates(&rolled));
+            chances[self.get_level(roll)] += p;
+        }
+        chances
+    }
+
+    // WFRP: the exact chance of every Success Level, keeping +0 apart from -0.
+    pub fn get_success_level_chances(&self) -> HashMap<(bool, i16), f64> {
+        let mut chances = HashMap::new();
+        for roll in 1..=100 {
+            let key = (self.is_wfrp_success(roll), self.get_success_levels(roll));
+            *chances.entry(key).or_insert(0.) += 0.01;
+        }
+        chances
+    }
+}
+
+// The chance of reaching each level, and at least that level.
+pub struct PercentileDisplay {
+    text: String,
+}
+
+impl PercentileDisplay {
+    pub fn new(test: &Percentile) -> Self {
+        let levels = test.get_system().get_levels();
+        let chances = test.get_chances();
+        let successes = test.get_system().get_successes();
+        let mut text = format!("\n{}\n", "Level\t\t\tProbability\tAt least".bold());
+        let mut at_least = 0.;
+        for (i, (level, p)) in levels.iter().zip(&chances).enumerate() {
+            at_least += p;
+            let line = format!(
+                "{:<20}\t{:>8.4} %\t{:>8.4} %",
+                level,
+                p * 100.,
+                at_least * 100.
+            );
+            text += &if i < successes {
+                format!("{}\n", line.green())
+            } else {
+                format!("{}\n", line.dark_red())
+            };
+        }
+        text += &format!(
+            "\nProbability of success:\t{} %\n",
+            format!("{:>8.4}", chances[..successes].iter().sum::<f64>() * 100.).bold()
+        );
+        if test.get_system() == System::Wfrp {
+            text += &format!("\n{}\n", "SL\tProbability".bold());
+            let sl_chances = test.get_success_level_chances();
+            let mut sls: Vec<&(bool, i16)> = sl_chances.keys().collect();
+            sls.sort_unstable_by(|a, b| b.cmp(a));
+            for sl in sls {
+                let label = match sl {
+                    (false, 0) => "-0".to_string(),
+                    (_, sl) => format!("{:+}", sl),
+                };
+                text += &format!("{}\t{:>8.4} %\n", label, sl_chances[sl] * 100.);
+            }
+        }
+        Self { text }
+    }
+}
+
+impl Component for PercentileDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        let coc = Percentile::new(System::Coc, 60, 0);
+        assert_eq!(coc.get_level(12), 1);
+        assert_eq!(coc.get_level(30), 2);
+        assert_eq!(coc.get_level(97), 4);
+        assert_eq!(coc.get_level(100), 5);
+        let chances = coc.get_chances();
+        assert!((chances[..4].iter().sum::<f64>() - 0.6).abs() < 1e-12);
+
+        let bonus = Percentile::new(System::Coc, 50, 1).get_chances();
+        assert!((bonus[..4].iter().sum::<f64>() - 0.75).abs() < 1e-12);
+
+        let wfrp = Percentile::new(System::Wfrp, 45, 0);
+        assert_eq!(wfrp.get_success_levels(21), 2);
+        assert_eq!(wfrp.get_success_levels(67), -2);
+        assert_eq!(wfrp.get_level(3), 1);
+        assert_eq!(Percentile::new(System::Wfrp, 2, 0).get_level(4), 3);
+
+        let mothership = Percentile::new(System::Mothership, 40, 0);
+        assert_eq!(mothership.get_level(33), 0);
+        assert_eq!(mothership.get_level(55), 3);
+        assert_eq!(mothership.get_level(40), 2);
+    }
+}
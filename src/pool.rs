@@ -0,0 +1,76 @@
+use crate::roll::DiceGroup;
+
+// An itemized dice pool like "agility:5 + pistols:6 + smartlink:2 - wounds:2 - recoil:1".
+// Every item changes the number of dice; nothing here touches the total of the roll.
+pub struct Pool {
+    items: Vec<(String, i16)>,
+}
+
+impl Pool {
+    pub fn from(expression: &str) -> Option<Self> {
+        let mut items = vec![];
+        let mut sign = 1;
+        let mut term = String::new();
+        for c in expression.chars() {
+            // a minus only separates items after a count, so labels like "smart-link" survive
+            let separator = c == '+'
+                || c == '-'
+                    && (term.trim().is_empty()
+                        || term.trim_end().ends_with(|t: char| t.is_ascii_digit()));
+            if separator {
+                if !term.trim().is_empty() {
+                    items.push(parse_item(&term, sign)?);
+                }
+                sign = if c == '-' { -1 } else { 1 };
+                term.clear();
+            } else {
+                term.push(c);
+            }
+        }
+        if !term.trim().is_empty() {
+            items.push(parse_item(&term, sign)?);
+        }
+        if items.is_empty() {
+            return None;
+        }
+        Some(Self { items })
+    }
+
+    pub fn to_dice_group(&self, sides: u16, hit: u16) -> DiceGroup {
+        DiceGroup::from_ledger(&self.items, sides, hit)
+    }
+}
+
+fn parse_item(term: &str, sign: i16) -> Option<(String, i16)> {
+    let (label, count) = term.trim().rsplit_once(':')?;
+    let count = count.trim().parse::<i16>().ok()?;
+    Some((label.trim().to_string(), sign * count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_items() {
+        let pool =
+            Pool::from("agility:5 + pistols:6 + smart-link:2 - wounds:2 - recoil:1").unwrap();
+        assert_eq!(
+            pool.items,
+            vec![
+                ("agility".to_string(), 5),
+                ("pistols".to_string(), 6),
+                ("smart-link".to_string(), 2),
+                ("wounds".to_string(), -2),
+                ("recoil".to_string(), -1),
+            ]
+        );
+        assert_eq!(pool.to_dice_group(6, 5).get_count(), 10);
+    }
+
+    #[test]
+    fn test_pool_invalid() {
+        assert!(Pool::from("agility + pistols:6").is_none());
+        assert!(Pool::from("").is_none());
+    }
+}
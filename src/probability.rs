@@ -7,14 +7,18 @@ use std::{
 
 use crossterm::{
     queue,
-    style::{Print, PrintStyledContent, ResetColor, Stylize},
+    style::{Print, PrintStyledContent, ResetColor, StyledContent, Stylize},
 };
 use itertools::Itertools;
 
 use crate::{
+    bands::Bands,
     components::Component,
     drawterm::{self, get_horizontal_fraction},
-    roll::DiceGroup,
+    genesys::{self, SymbolDie, Symbols},
+    roll::{DiceGroup, Keep},
+    rules::Degree,
+    yze::{Kind, Pools},
 };
 
 #[derive(Debug)]
@@ -41,6 +45,87 @@ impl Polynomial {
         self.coefficients.insert(exponent, value);
     }
 
+    // The highest or lowest of two independent rolls of this distribution.
+    pub fn keep(&self, keep: Keep) -> Polynomial {
+        match keep {
+            Keep::Highest => self.max(self),
+            Keep::Lowest => self.min(self),
+        }
+    }
+
+    // The higher of a roll from each of two independent distributions.
+    pub fn max(&self, other: &Polynomial) -> Polynomial {
+        let mut result = Polynomial::new();
+        let (mut below, mut other_below) = (0., 0.);
+        for exponent in self.get_exponents(other) {
+            let (p, q) = (
+                self.get_coefficient(exponent),
+                other.get_coefficient(exponent),
+            );
+            let value = (below + p) * (other_below + q) - below * other_below;
+            result.set_coefficient(exponent, value);
+            below += p;
+            other_below += q;
+        }
+        result
+    }
+
+    // The lower of a roll from each of two independent distributions.
+    pub fn min(&self, other: &Polynomial) -> Polynomial {
+        let mut result = Polynomial::new();
+        let (mut above, mut other_above) = (0., 0.);
+        for exponent in self.get_exponents(other).into_iter().rev() {
+            let (p, q) = (
+                self.get_coefficient(exponent),
+                other.get_coefficient(exponent),
+            );
+            let value = (above + p) * (other_above + q) - above * other_above;
+            result.set_coefficient(exponent, value);
+            above += p;
+            other_above += q;
+        }
+        result
+    }
+
+    fn get_exponents(&self, other: &Polynomial) -> Vec<u16> {
+        self.coefficients
+            .keys()
+            .chain(other.coefficients.keys())
+            .unique()
+            .sorted()
+            .copied()
+            .collect()
+    }
+
+    // A die that rolls again and adds on its highest face, cut off once the chance is negligible.
+    pub fn exploding(sides: u16) -> Polynomial {
+        let mut result = Polynomial::new();
+        if sides <= 1 {
+            result.set_coefficient(sides, 1.);
+            return result;
+        }
+        let face = 1. / sides as f64;
+        let mut chain = face;
+        let mut base = 0;
+        while chain > 1e-12 {
+            for roll in 1..sides {
+                result.set_coefficient(base + roll, chain);
+            }
+            base += sides;
+            chain *= face;
+        }
+        result
+    }
+
+    // Every exponent moved up by an offset and every coefficient scaled by a weight.
+    fn shift(&self, offset: u16, weight: f64) -> Polynomial {
+        let mut result = Polynomial::new();
+        for (exponent, p) in &self.coefficients {
+            result.set_coefficient(exponent + offset, p * weight);
+        }
+        result
+    }
+
     pub fn pow(&self, exponent: u16) -> Polynomial {
         let copy = self.clone();
         let mut result = self.clone();
@@ -71,14 +156,9 @@ impl Display for Polynomial {
 
 impl ops::AddAssign for Polynomial {
     fn add_assign(&mut self, rhs: Self) {
-        let mut new = Polynomial::new();
-        for i in self.get_coefficients().keys() {
-            new.set_coefficient(*i, self.get_coefficient(*i) + rhs.get_coefficient(*i));
-        }
-        for i in rhs.get_coefficients().keys() {
-            new.set_coefficient(*i, self.get_coefficient(*i) + rhs.get_coefficient(*i));
+        for (i, c) in rhs.coefficients {
+            *self.coefficients.entry(i).or_insert(0.) += c;
         }
-        self.clone_from(&new);
     }
 }
 
@@ -169,15 +249,47 @@ impl TotalGraph {
     }
 }
 
+impl Total {
+    // Totals that don't come straight from one group of dice, like a round of attacks.
+    pub fn from_polynomial(polynomial: Polynomial) -> Self {
+        Self {
+            polynomial,
+            dice: DiceGroup::default(),
+        }
+    }
+}
+
+impl Total {
+    // The chance of a total of at least this much once the result modifiers are applied.
+    pub fn get_probability_of_total(&self, total: u16) -> f64 {
+        self.polynomial
+            .get_coefficients()
+            .iter()
+            .filter(|(roll, _)| self.dice.get_total(**roll) >= total)
+            .map(|(_, p)| p)
+            .sum()
+    }
+}
+
 impl Probability for Total {
     fn from_dice(dice: &DiceGroup) -> Self {
-        let num = dice.get_count();
+        let num = dice.get_kept_count();
         let sides = dice.get_sides().unwrap_or(1);
         let mut poly = Polynomial::new();
         for i in 1..=sides {
             poly.set_coefficient(i, 1.0 / sides as f64);
         }
-        poly = poly.pow(num);
+        poly = match dice.get_keep() {
+            Some(keep) => poly.keep(keep),
+            None if dice.is_wild() => Polynomial::exploding(sides).max(&Polynomial::exploding(6)),
+            None => poly,
+        };
+        poly = match dice.get_roll_keep() {
+            Some(keep) => {
+                get_keep_highest_of(&Polynomial::exploding(sides), dice.get_count(), keep)
+            }
+            None => poly.pow(num),
+        };
         Total {
             polynomial: poly,
             dice: dice.clone(),
@@ -203,7 +315,7 @@ impl Probability for Total {
             .sorted()
             .map(|entry| {
                 (
-                    *entry + self.dice.get_total_modifier(),
+                    self.dice.get_total(*entry),
                     100. * self.get_probability_of(*entry) as f32,
                 )
             })
@@ -267,30 +379,34 @@ impl HitsGraph {
 }
 
 impl Hits {
+    pub fn from_data(data: HashMap<u16, f64>) -> Self {
+        Self { data }
+    }
+
     // For n dice of r sides with success on s sides,
     // prob(x successes) = (n choose x)*((s/r)^x)*((r-s)/r)^(n-x)
     fn create_data(dice: &DiceGroup) -> HashMap<u16, f64> {
         let mut data = HashMap::new();
         let sides = dice.get_sides().unwrap_or(1);
-        let success_sides = if dice.get_hit() <= sides {
+        let success_sides = if dice.is_chance() {
+            1
+        } else if dice.get_hit() <= sides {
             sides - (dice.get_hit() - 1)
         } else {
             0
         };
-        let n = dice.get_count();
-        for x in 0..=dice.get_count() {
-            let mut coeff: f64 = 1.;
-
-            for i in dice.get_count() - x + 1..=dice.get_count() {
-                coeff *= i as f64;
-            }
-            for i in 1..=x {
-                coeff /= i as f64;
-            }
-
-            let dice_factor =
-                ((sides - success_sides) as f64 / sides as f64).powi(n as i32 - x as i32);
-            let succ_factor = (success_sides as f64 / sides as f64).powi(x as i32);
+        let n = dice.get_kept_count();
+        let p = success_sides as f64 / sides as f64;
+        // a kept pair hits if either die does with advantage, only if both do with disadvantage
+        let p = match dice.get_keep() {
+            Some(Keep::Highest) => 1. - (1. - p).powi(2),
+            Some(Keep::Lowest) => p.powi(2),
+            None => p,
+        };
+        for x in 0..=n {
+            let coeff = choose(n, x);
+            let dice_factor = (1. - p).powi(n as i32 - x as i32);
+            let succ_factor = p.powi(x as i32);
             let value: f64 = coeff * succ_factor * dice_factor;
             data.insert(x, value);
         }
@@ -370,29 +486,76 @@ impl Component for HitsGraph {
 
 pub struct SummaryDisplay {
     text: String,
+    success: f64,
 }
 
 impl SummaryDisplay {
-    pub fn new(dice: &DiceGroup, hitnum: Option<u16>, totalnum: Option<u16>) -> Self {
+    pub fn new(
+        dice: &DiceGroup,
+        hitnum: Option<u16>,
+        totalnum: Option<u16>,
+        dc: Option<u16>,
+        raises: u16,
+    ) -> Self {
+        if dice.is_wild() {
+            return Self::from_wild(dice, dc.unwrap_or(4));
+        }
+        if let Some(pools) = dice.get_yze() {
+            return Self::from_yze(pools);
+        }
+        if !dice.get_symbol_dice().is_empty() {
+            return Self::from_symbols(dice.get_symbol_dice());
+        }
+        if dice.get_roll_keep().is_some() {
+            return Self::from_roll_keep(dice, dc, raises);
+        }
         let hits = hitnum.unwrap_or(u16::MAX);
         let total = totalnum.unwrap_or(u16::MAX);
         let hitsummary = Hits::from_dice(dice);
         let totalsummary = Total::from_dice(dice);
-        let glitchdice = DiceGroup::new(dice.dice.clone(), dice.get_sides().unwrap_or(u16::MAX));
+        let mut glitchdice =
+            DiceGroup::new(dice.dice.clone(), dice.get_sides().unwrap_or(u16::MAX));
+        // counting ones on the kept die is counting highest faces with the other die kept
+        glitchdice.set_keep(dice.get_keep().map(|keep| match keep {
+            Keep::Highest => Keep::Lowest,
+            Keep::Lowest => Keep::Highest,
+        }));
         let glitchsummary = Hits::from_dice(&glitchdice);
         let successchance_hit = hitsummary.get_probability_of_gt(hits);
-        let successchance_total =
-            totalsummary.get_probability_of_gt(total - dice.get_total_modifier());
-        let glitchchance =
-            glitchsummary.get_probability_of_gt((dice.get_count() as f32 / 2.).round() as u16);
-        let critglitchchance = (1.0 - successchance_hit) * glitchchance;
+        let successchance_total = totalsummary.get_probability_of_total(total);
+        // an auto-failed empty pool has no dice to glitch with
+        let glitchchance = if dice.get_count() == 0 {
+            0.
+        } else {
+            glitchsummary.get_probability_of_gt((dice.get_kept_count() as f32 / 2.).round() as u16)
+        };
+        // a one on the chance die is always a failure too
+        let critglitchchance = if dice.is_chance() {
+            glitchchance
+        } else {
+            (1.0 - successchance_hit) * glitchchance
+        };
+        let dc = dc.filter(|_| dice.get_sides() == Some(20));
+        let success = if let Some(dc) = dc {
+            get_probability_vs_dc(dice, &totalsummary, dc)
+        } else if hits != u16::MAX {
+            successchance_hit
+        } else if total != u16::MAX {
+            successchance_total
+        } else {
+            hitsummary.get_probability_of_gt(1)
+        };
         let success_hit: String = format!("{:>8.4}", successchance_hit as f32 * 100.);
         let success_total: String = format!("{:>8.4}", successchance_total as f32 * 100.);
         let glitch: String = format!("{:>8.4}", glitchchance as f32 * 100.);
         let critglitch: String = format!("{:>8.4}", critglitchchance as f32 * 100.);
-        let text: String;
+        let mut text: String = dice
+            .get_adjustments()
+            .iter()
+            .map(|(label, delta)| format!("\n{}:\t\t{:+} dice", label, delta))
+            .collect();
         if hits != u16::MAX && total != u16::MAX {
-            text = format!("\nProbability of {} total:\t\t{} %\nProbability of {} hits:\t\t{} %\nProbability of glitch:\t\t{} %\nProbability of critical glitch:\t{} %\n",
+            text += &format!("\nProbability of {} total:\t\t{} %\nProbability of {} hits:\t\t{} %\nProbability of glitch:\t\t{} %\nProbability of critical glitch:\t{} %\n",
                 total,
                 success_total.bold(),
                 hits,
@@ -400,23 +563,178 @@ impl SummaryDisplay {
                 glitch.bold().dark_yellow(),
                 critglitch.bold().dark_red());
         } else if hits != u16::MAX {
-            text = format!("\nProbability of success:\t\t{} %\nProbability of glitch:\t\t{} %\nProbability of critical glitch:\t{} %\n",
+            text += &format!("\nProbability of success:\t\t{} %\nProbability of glitch:\t\t{} %\nProbability of critical glitch:\t{} %\n",
                 success_hit.bold(),
                 glitch.bold().dark_yellow(),
                 critglitch.bold().dark_red());
         } else if total != u16::MAX {
-            text = format!("\nProbability of success:\t\t{} %\nProbability of glitch:\t\t{} %\nProbability of critical glitch:\t{} %\n",
+            text += &format!("\nProbability of success:\t\t{} %\nProbability of glitch:\t\t{} %\nProbability of critical glitch:\t{} %\n",
                 success_total.bold(),
                 glitch.bold().dark_yellow(),
                 critglitch.bold().dark_red());
         } else {
-            text = format!(
+            text += &format!(
                 "\nProbability of glitch:\t\t{} %\nProbability of critical glitch:\t{} %\n",
                 glitch.bold().dark_yellow(),
                 critglitch.bold().dark_red()
             );
         }
-        Self { text }
+        if let Some(dc) = dc {
+            text += &format!(
+                "Probability vs DC {}:\t\t{} %\n",
+                dc,
+                format!("{:>8.4}", success * 100.).bold()
+            );
+        }
+        Self { text, success }
+    }
+
+    // Savage Worlds odds against a target number. The kept die only shows a one on snake eyes,
+    // which fails whatever the total.
+    fn from_wild(dice: &DiceGroup, tn: u16) -> Self {
+        let totals = Total::from_dice(dice);
+        let at_least = |target: u16| -> f64 {
+            totals
+                .polynomial
+                .get_coefficients()
+                .iter()
+                .filter(|(roll, _)| **roll > 1 && dice.get_total(**roll) >= target)
+                .map(|(_, p)| p)
+                .sum()
+        };
+        let success = at_least(tn);
+        let text = format!(
+            "\nProbability of success vs TN {}:\t{} %\nProbability of a raise:\t\t{} %\nProbability of two raises:\t{} %\nProbability of critical failure:\t{} %\n",
+            tn,
+            format!("{:>8.4}", success * 100.).bold(),
+            format!("{:>8.4}", at_least(tn + 4) * 100.).bold().green(),
+            format!("{:>8.4}", at_least(tn + 8) * 100.).bold().green(),
+            format!("{:>8.4}", totals.get_probability_of(1) * 100.).bold().dark_red()
+        );
+        Self { text, success }
+    }
+
+    // The chance of the symbols that matter once a Genesys roll is cancelled down.
+    fn from_symbols(dice: &[SymbolDie]) -> Self {
+        let results = genesys::get_distribution(dice);
+        let chance = |check: &dyn Fn(&Symbols) -> bool| -> f64 {
+            results
+                .iter()
+                .filter(|(symbols, _)| check(symbols))
+                .fold(0., |sum, (_, p)| sum + p)
+        };
+        let success = chance(&|symbols| symbols.is_success());
+        let line =
+            |label: &str, p: f64| format!("{}\t{} %\n", label, format!("{:>8.4}", p * 100.).bold());
+        let mut text = "\n".to_string();
+        text += &line("Probability of success:\t\t", success);
+        text += &line(
+            "Probability of advantage:\t",
+            chance(&|symbols| symbols.advantage >= 1),
+        );
+        text += &line(
+            "Probability of 2+ advantage:\t",
+            chance(&|symbols| symbols.advantage >= 2),
+        );
+        text += &line(
+            "Probability of threat:\t\t",
+            chance(&|symbols| symbols.advantage <= -1),
+        );
+        text += &line(
+            "Probability of triumph:\t\t",
+            chance(&|symbols| symbols.triumph > 0),
+        );
+        text += &line(
+            "Probability of despair:\t\t",
+            chance(&|symbols| symbols.despair > 0),
+        );
+        text += &line(
+            "Success with advantage:\t\t",
+            chance(&|symbols| symbols.is_success() && symbols.advantage >= 1),
+        );
+        Self { text, success }
+    }
+
+    // Year Zero successes before and after pushing every die that can be, and the banes
+    // a push risks.
+    fn from_yze(pools: Pools) -> Self {
+        let before = pools.get_successes(false);
+        let after = pools.get_successes(true);
+        let mut text = format!("\n{}\n", "Successes\tBefore push\tAfter push".bold());
+        for successes in 0..=pools.get_count() {
+            text += &format!(
+                "{}\t\t{:>8.4} %\t{:>8.4} %\n",
+                successes,
+                before.get_coefficient(successes) * 100.,
+                after.get_coefficient(successes) * 100.
+            );
+        }
+        let success = 1. - before.get_coefficient(0);
+        let banes = |kind: Kind| pools.get(kind) as f64 * kind.get_bane_chance(true);
+        text += &format!(
+            "\nProbability of success:\t\t{} %\nProbability after pushing:\t{} %\nExpected trauma after pushing:\t{}\nExpected gear damage after pushing:\t{}\n",
+            format!("{:>8.4}", success * 100.).bold(),
+            format!("{:>8.4}", (1. - after.get_coefficient(0)) * 100.).bold().green(),
+            format!("{:.2}", banes(Kind::Base)).bold().dark_red(),
+            format!("{:.2}", banes(Kind::Gear)).bold().dark_yellow()
+        );
+        Self { text, success }
+    }
+
+    // Roll-and-keep odds of reaching a TN with every raise called adding 5 to it,
+    // or of a spread of TNs when none is given.
+    fn from_roll_keep(dice: &DiceGroup, tn: Option<u16>, raises: u16) -> Self {
+        let totals = Total::from_dice(dice);
+        let at_least = |target: u16| totals.get_probability_of_total(target);
+        let average: f64 = totals
+            .polynomial
+            .get_coefficients()
+            .iter()
+            .map(|(total, p)| dice.get_total(*total) as f64 * p)
+            .sum();
+        let mut text = format!(
+            "\nAverage total:\t\t\t{}\n",
+            format!("{:.2}", average).bold()
+        );
+        let success = match tn {
+            Some(tn) => {
+                for called in 0..=raises.max(3) {
+                    let called_label = match called {
+                        0 => "no raises".to_string(),
+                        1 => "1 raise".to_string(),
+                        called => format!("{} raises", called),
+                    };
+                    let line = format!(
+                        "Probability of TN {} ({}):\t{} %\n",
+                        tn + 5 * called,
+                        called_label,
+                        format!("{:>8.4}", at_least(tn + 5 * called) * 100.).bold()
+                    );
+                    text += &if called == raises {
+                        format!("{}", line.green())
+                    } else {
+                        line
+                    };
+                }
+                at_least(tn + 5 * raises)
+            }
+            None => {
+                for target in (10..=40).step_by(5) {
+                    text += &format!(
+                        "Probability of TN {}:\t\t{} %\n",
+                        target,
+                        format!("{:>8.4}", at_least(target) * 100.).bold()
+                    );
+                }
+                at_least(15)
+            }
+        };
+        Self { text, success }
+    }
+
+    // The chance the summary reports as success, or of at least one hit when no target was given.
+    pub fn get_success_chance(&self) -> f64 {
+        self.success
     }
 }
 
@@ -427,6 +745,194 @@ impl Component for SummaryDisplay {
     }
 }
 
+// The chance of each PF2e degree of success against a DC, drawn as one stacked bar.
+pub struct DegreeDisplay {
+    text: String,
+}
+
+impl DegreeDisplay {
+    pub fn new(dice: &DiceGroup, dc: u16) -> Self {
+        let totals = Total::from_dice(dice);
+        let lone = dice.get_kept_count() == 1 && dice.get_sides() == Some(20);
+        let mut chances = [0.; 4];
+        for (roll, p) in totals.polynomial.get_coefficients() {
+            let natural = if lone { Some(*roll) } else { None };
+            chances[Degree::from(dice.get_total(*roll), natural, dc) as usize] += p;
+        }
+
+        let width = drawterm::get_width() / 2;
+        let mut text = format!("\nDegrees of success against DC {}:\n", dc);
+        for degree in Degree::ALL {
+            let bar: String = get_horizontal_bar(chances[degree as usize] as f32 * width as f32)
+                .iter()
+                .collect();
+            text += &format!("{}", get_degree_style(degree, bar));
+        }
+        text += "\n";
+        for degree in Degree::ALL {
+            text += &format!(
+                "{}\t{} %\n",
+                get_degree_style(degree, format!("{:<16}", degree.get_label())),
+                format!("{:>8.4}", chances[degree as usize] * 100.).bold()
+            );
+        }
+        Self { text }
+    }
+}
+
+fn get_degree_style(degree: Degree, text: String) -> StyledContent<String> {
+    match degree {
+        Degree::CriticalSuccess => text.bold().green(),
+        Degree::Success => text.dark_green(),
+        Degree::Failure => text.dark_yellow(),
+        Degree::CriticalFailure => text.bold().dark_red(),
+    }
+}
+
+impl Component for DegreeDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+// The chance of landing in each outcome band, as a segmented bar.
+pub struct BandDisplay {
+    text: String,
+}
+
+impl BandDisplay {
+    pub fn new(dice: &DiceGroup, bands: &Bands) -> Self {
+        let totals = Total::from_dice(dice);
+        let mut chances = vec![0.; bands.get_count()];
+        for (roll, p) in totals.polynomial.get_coefficients() {
+            chances[bands.get_band(dice.get_total(*roll))] += p;
+        }
+
+        let width = drawterm::get_width() / 2;
+        let mut text = "\nOutcome bands:\n".to_string();
+        for (band, chance) in chances.iter().enumerate().rev() {
+            let bar: String = get_horizontal_bar(*chance as f32 * width as f32)
+                .iter()
+                .collect();
+            text += &format!("{}", bands.get_style(band, bar));
+        }
+        text += "\n";
+        for (band, chance) in chances.iter().enumerate().rev() {
+            text += &format!(
+                "{}\t{} %\n",
+                bands.get_style(band, format!("{:<24}", bands.get_label(band))),
+                format!("{:>8.4}", chance * 100.).bold()
+            );
+        }
+        Self { text }
+    }
+}
+
+impl Component for BandDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+// A natural 20 on a lone d20 always succeeds and a natural 1 always fails.
+fn get_probability_vs_dc(dice: &DiceGroup, totals: &Total, dc: u16) -> f64 {
+    let lone = dice.get_kept_count() == 1;
+    totals
+        .polynomial
+        .get_coefficients()
+        .iter()
+        .filter(|(roll, _)| match **roll {
+            20 if lone => true,
+            1 if lone => false,
+            roll => dice.get_total(roll) >= dc,
+        })
+        .map(|(_, p)| p)
+        .sum()
+}
+
+// The sum of the highest `keep` of `dice` dice with `sides` sides, like 4d6 drop lowest.
+pub fn get_keep_highest(dice: u16, sides: u16, keep: u16) -> Polynomial {
+    let mut die = Polynomial::new();
+    for face in 1..=sides {
+        die.set_coefficient(face, 1. / sides as f64);
+    }
+    get_keep_highest_of(&die, dice, keep)
+}
+
+// The same for any die, like an exploding d10 for roll-and-keep. Works down from the
+// highest value, choosing how many dice show each one. Once enough dice are kept the rest
+// only have to come up lower, so those rolls are finished off straight away.
+pub fn get_keep_highest_of(die: &Polynomial, dice: u16, keep: u16) -> Polynomial {
+    let keep = keep.min(dice);
+    let mut start = Polynomial::new();
+    start.set_coefficient(0, 1.);
+    if keep == 0 {
+        return start;
+    }
+    let mut states: HashMap<(u16, u16), Polynomial> = HashMap::new();
+    states.insert((0, 0), start);
+    let mut result = Polynomial::new();
+    // the chance of a die coming up lower than every face so far
+    let mut below: f64 = 1.;
+    for face in die.get_coefficients().keys().sorted().rev() {
+        let p = die.get_coefficient(*face);
+        below = (below - p).max(0.);
+        // most dice used first, so every state moves on before anything moves into it
+        let keys: Vec<(u16, u16)> = states.keys().sorted().rev().copied().collect();
+        for (used, kept) in keys {
+            let need = keep - kept;
+            let mut finished = 0.;
+            for count in 1..=dice - used {
+                let weight = choose(dice - used, count) * p.powi(count as i32);
+                if count >= need {
+                    finished += weight * below.powi((dice - used - count) as i32);
+                    continue;
+                }
+                // the long explosion chains are too unlikely to matter
+                if weight < 1e-15 {
+                    continue;
+                }
+                let moved = states[&(used, kept)].shift(face * count, weight);
+                *states
+                    .entry((used + count, kept + count))
+                    .or_insert_with(Polynomial::new) += moved;
+            }
+            if finished >= 1e-15 {
+                result += states[&(used, kept)].shift(face * need, finished);
+            }
+        }
+    }
+    result
+}
+
+// The totals of a roll once the group's result modifiers are applied, which like the
+// roller never takes a total below zero.
+pub fn apply_modifiers(rolled: &Polynomial, dice: &DiceGroup) -> Polynomial {
+    let mut totals = Polynomial::new();
+    for (roll, p) in rolled.get_coefficients() {
+        let total = dice.get_total(*roll);
+        totals.set_coefficient(total, totals.get_coefficient(total) + p);
+    }
+    totals
+}
+
+// n choose k, as a float since it only ever feeds probabilities
+pub fn choose(n: u16, k: u16) -> f64 {
+    if k > n {
+        return 0.;
+    }
+    let mut coeff: f64 = 1.;
+    for i in n - k + 1..=n {
+        coeff *= i as f64;
+    }
+    for i in 1..=k {
+        coeff /= i as f64;
+    }
+    coeff
+}
+
 fn get_horizontal_bar(value: f32) -> Vec<char> {
     let mut result = vec!['█'; value as usize];
     let len = result.len();
@@ -456,6 +962,50 @@ mod tests {
         assert_eq!(p1 + p2, p3);
     }
 
+    #[test]
+    fn test_polynomial_keep() {
+        let mut d2 = Polynomial::new();
+        d2.set_coefficient(1, 0.5);
+        d2.set_coefficient(2, 0.5);
+        let mut highest = Polynomial::new();
+        highest.set_coefficient(1, 0.25);
+        highest.set_coefficient(2, 0.75);
+        let mut lowest = Polynomial::new();
+        lowest.set_coefficient(1, 0.75);
+        lowest.set_coefficient(2, 0.25);
+
+        assert_eq!(d2.keep(Keep::Highest), highest);
+        assert_eq!(d2.keep(Keep::Lowest), lowest);
+    }
+
+    #[test]
+    fn test_keep_highest() {
+        let scores = get_keep_highest(4, 6, 3);
+        let total: f64 = scores.get_coefficients().values().sum();
+        assert!((total - 1.).abs() < 1e-9);
+        assert!((scores.get_coefficient(18) - 21. / 1296.).abs() < 1e-12);
+        assert!((scores.get_coefficient(3) - 1. / 1296.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_roll_and_keep() {
+        let d10 = Polynomial::exploding(10);
+        let kept = get_keep_highest_of(&d10, 2, 1);
+        let total: f64 = kept.get_coefficients().values().sum();
+        assert!((total - 1.).abs() < 1e-9);
+        assert!((kept.get_coefficient(1) - 1. / 100.).abs() < 1e-12);
+        assert!((kept.get_coefficient(9) - 17. / 100.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_polynomial_exploding() {
+        let d4 = Polynomial::exploding(4);
+        let total: f64 = d4.get_coefficients().values().sum();
+        assert!((total - 1.).abs() < 1e-9);
+        assert_eq!(d4.get_coefficient(4), 0.);
+        assert!((d4.get_coefficient(5) - 1. / 16.).abs() < 1e-12);
+    }
+
     #[test]
     fn test_polynomial_sub() {
         let mut p1 = Polynomial::new();
@@ -1,3 +1,10 @@
+use crate::{
+    bands::Bands,
+    blades::Action,
+    genesys::{self, SymbolDie, Symbols},
+    rules::{Degree, Rules},
+    yze::{Kind, Pools},
+};
 use crossterm::style::Stylize;
 use std::{
     fmt::{Display, Write},
@@ -12,6 +19,35 @@ pub struct Result {
     pub value: u16,
     pub sides: u16,
     pub modifier: u16,
+    // rolled beyond the original pool, by rerolls or Edge
+    pub extra: bool,
+    // the lower die with advantage or the higher with disadvantage, shown but not counted
+    pub dropped: bool,
+    // rolled because the die before it aced, and added to it
+    pub ace: bool,
+    // the kind of die in a Year Zero pool
+    pub kind: Option<Kind>,
+    // a Genesys symbol die, whose value is the face rolled
+    pub symbol: Option<SymbolDie>,
+}
+
+impl Result {
+    // A plain die showing a value, for rolls read some other way than by hits.
+    pub fn new(value: u16, sides: u16) -> Self {
+        Self {
+            critfail: false,
+            crit: false,
+            hit: false,
+            value,
+            sides,
+            modifier: 0,
+            extra: false,
+            dropped: false,
+            ace: false,
+            kind: None,
+            symbol: None,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -22,6 +58,21 @@ pub struct Summary {
     pub crits: u16,
     pub total: u16,
     pub total_modifier: u16,
+    // result modifiers taken off the total
+    penalty: u16,
+    // read by outcome bands, which have no glitches
+    banded: bool,
+    // read by its notes alone, like an Ironsworn action roll
+    no_total: bool,
+    // not a pool, so there are no hits to show, like a 40k damage roll
+    no_hits: bool,
+    adjustments: Vec<(String, i16)>,
+    pool: u16,
+    empty: Option<EmptyPool>,
+    label: Option<String>,
+    limit: Option<(u16, u16)>,
+    notes: Vec<String>,
+    rules: Rules,
 }
 
 impl Summary {
@@ -33,10 +84,25 @@ impl Summary {
             crits: 0,
             total: 0,
             total_modifier: 0,
+            adjustments: vec![],
+            penalty: 0,
+            banded: false,
+            no_total: false,
+            no_hits: false,
+            pool: 0,
+            empty: None,
+            label: None,
+            limit: None,
+            notes: vec![],
+            rules: Rules::Sr5,
         }
     }
 
     pub fn add_result(&mut self, result: Result) {
+        if result.dropped {
+            self.results.push(result);
+            return;
+        }
         self.hits += if result.hit { 1 } else { 0 };
         self.crits += if result.crit { 1 } else { 0 };
         self.total += result.value;
@@ -48,20 +114,367 @@ impl Summary {
         &self.results
     }
 
-    pub fn get_glitch(&self) -> bool {
-        let mut fails = 0;
-        for result in &self.results {
-            if result.critfail {
-                fails += 1;
+    pub fn add_modifier(&mut self, modifier: u16) {
+        self.total += modifier;
+        self.total_modifier += modifier;
+    }
+
+    pub fn subtract_modifier(&mut self, penalty: u16) {
+        self.total = self.total.saturating_sub(penalty);
+        self.penalty += penalty;
+    }
+
+    pub fn set_pool(&mut self, adjustments: &[(String, i16)], pool: u16, empty: Option<EmptyPool>) {
+        self.adjustments = adjustments.to_vec();
+        self.pool = pool;
+        self.empty = empty;
+    }
+
+    pub fn hide_total(&mut self) {
+        self.no_total = true;
+    }
+
+    pub fn hide_hits(&mut self) {
+        self.no_hits = true;
+    }
+
+    pub fn set_label(&mut self, label: &str) {
+        self.label = Some(label.to_string());
+    }
+
+    // Caps the hits at the limit while remembering what was actually rolled.
+    pub fn apply_limit(&mut self, limit: u16) {
+        self.limit = Some((limit, self.hits));
+        self.hits = self.hits.min(limit);
+    }
+
+    pub fn get_critical_glitch(&self) -> bool {
+        self.get_glitch() && self.hits == 0
+    }
+
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    pub fn add_note(&mut self, note: String) {
+        self.notes.push(note);
+    }
+
+    // Savage Worlds: meeting the target number is a success and every 4 over it a raise.
+    // A one on both the trait die and the wild die is a critical failure whatever the total.
+    pub fn check_raises(&mut self, tn: u16) {
+        let firsts: Vec<u16> = self
+            .results
+            .iter()
+            .filter(|result| !result.extra)
+            .map(|result| result.value)
+            .collect();
+        let note = if firsts == [1, 1] {
+            "Critical failure: snake eyes".to_string()
+        } else if self.total < tn {
+            format!("Failure against TN {}", tn)
+        } else {
+            match (self.total - tn) / 4 {
+                0 => format!("Success against TN {}", tn),
+                1 => format!("Success with a raise against TN {}", tn),
+                raises => format!("Success with {} raises against TN {}", raises, tn),
             }
-            if fails * 2 > self.results.len() {
-                return true;
+        };
+        self.add_note(note);
+    }
+
+    // Blades in the Dark reads the highest die kept rather than hits or the total.
+    pub fn check_action(&mut self) {
+        let kept: Vec<u16> = self
+            .results
+            .iter()
+            .filter(|result| !result.dropped)
+            .map(|result| result.value)
+            .collect();
+        self.add_note(Action::from(&kept).get_label().to_string());
+    }
+
+    // Year Zero: push the roll once, rerolling every die that isn't a six or a bane.
+    // The dice it replaces stay on show but stop counting.
+    pub fn push(&mut self) {
+        let pushable: Vec<usize> = (0..self.results.len())
+            .filter(|i| {
+                let result = &self.results[*i];
+                match result.kind {
+                    Some(kind) => {
+                        !result.dropped && result.value != 6 && !kind.is_bane(result.value)
+                    }
+                    None => false,
+                }
+            })
+            .collect();
+        for i in pushable.iter().rev() {
+            let before = self.results[*i];
+            self.results[*i].dropped = true;
+            self.total -= before.value;
+            let value = 1 + rand::random::<u16>() % before.sides;
+            let kind = before.kind.unwrap_or(Kind::Base);
+            let after = Result {
+                critfail: kind.is_bane(value),
+                hit: value == 6,
+                value,
+                extra: true,
+                ..before
+            };
+            self.total += value;
+            self.hits += if after.hit { 1 } else { 0 };
+            self.results.insert(i + 1, after);
+        }
+        self.add_note(format!("Pushed: rerolled {} dice", pushable.len()));
+    }
+
+    // Year Zero: every six is a success, and the banes are counted by kind.
+    pub fn check_yze(&mut self) {
+        let kept: Vec<(Kind, u16)> = self
+            .results
+            .iter()
+            .filter(|result| !result.dropped)
+            .filter_map(|result| result.kind.map(|kind| (kind, result.value)))
+            .collect();
+        let banes = |of: Kind| {
+            kept.iter()
+                .filter(|(kind, value)| *kind == of && kind.is_bane(*value))
+                .count()
+        };
+        let mut note = match kept.iter().filter(|(_, value)| *value == 6).count() {
+            0 => "Failure: no sixes".to_string(),
+            1 => "1 success".to_string(),
+            successes => format!("{} successes", successes),
+        };
+        if banes(Kind::Base) > 0 {
+            note += &format!(", {} trauma", banes(Kind::Base));
+        }
+        if banes(Kind::Gear) > 0 {
+            note += &format!(", {} gear damage", banes(Kind::Gear));
+        }
+        self.add_note(note);
+    }
+
+    // Genesys: the symbols left once failures cancel successes and threat cancels advantage.
+    pub fn get_symbols(&self) -> Symbols {
+        self.results
+            .iter()
+            .filter(|result| !result.dropped)
+            .filter_map(|result| {
+                result
+                    .symbol
+                    .map(|die| die.get_faces()[result.value as usize - 1])
+            })
+            .fold(Symbols::default(), |net, face| net.add(&face))
+    }
+
+    pub fn check_symbols(&mut self) {
+        let net = self.get_symbols();
+        let outcome = if net.is_success() {
+            "Success"
+        } else {
+            "Failure"
+        };
+        self.add_note(format!("{}: {}", outcome, net.get_label()));
+    }
+
+    // Labels the total with the band it lands in, like a PbtA weak hit.
+    pub fn check_bands(&mut self, bands: &Bands) {
+        self.add_note(bands.get_label(bands.get_band(self.total)));
+        self.banded = true;
+    }
+
+    // Roll-and-keep: every raise called before the roll adds 5 to the TN.
+    pub fn check_called_raises(&mut self, tn: u16, raises: u16) {
+        let target = tn + 5 * raises;
+        let outcome = if self.total >= target {
+            "Success"
+        } else {
+            "Failure"
+        };
+        self.add_note(match raises {
+            0 => format!("{} against TN {}", outcome, tn),
+            1 => format!("{} against TN {} with a raise", outcome, tn),
+            raises => format!("{} against TN {} with {} raises", outcome, tn, raises),
+        });
+    }
+
+    // Drops the lowest dice once they are rolled, like 4d6 drop lowest.
+    pub fn drop_lowest(&mut self, count: u16) {
+        let mut order: Vec<usize> = (0..self.results.len())
+            .filter(|i| !self.results[*i].dropped)
+            .collect();
+        order.sort_by_key(|i| self.results[*i].value);
+        for i in order.into_iter().take(count as usize) {
+            let result = &mut self.results[i];
+            result.dropped = true;
+            self.total -= result.value;
+            self.total_modifier -= result.modifier;
+            self.hits -= if result.hit { 1 } else { 0 };
+            self.crits -= if result.crit { 1 } else { 0 };
+        }
+    }
+
+    // Compares the total against a DC. A natural 20 or 1 on a lone d20 settles it regardless,
+    // or under PF2e shifts the degree of success by a step.
+    pub fn check_dc(&mut self, dc: u16) {
+        let kept: Vec<&Result> = self
+            .results
+            .iter()
+            .filter(|result| !result.dropped)
+            .collect();
+        let natural = match kept[..] {
+            [result] if result.sides == 20 => Some(result.value - result.modifier),
+            _ => None,
+        };
+        let margin = self.total as i32 - dc as i32;
+        if self.rules == Rules::Pf2e {
+            let degree = Degree::from(self.total, natural, dc);
+            self.add_note(format!(
+                "{} against DC {} ({:+})",
+                degree.get_label(),
+                dc,
+                margin
+            ));
+            return;
+        }
+        let note = match natural {
+            Some(20) => format!("Natural 20: success against DC {}", dc),
+            Some(1) => format!("Natural 1: failure against DC {}", dc),
+            _ if margin >= 0 => format!("Success against DC {} by {}", dc, margin),
+            _ => format!("Failure against DC {} by {}", dc, -margin),
+        };
+        self.add_note(note);
+    }
+
+    // More than half the dice showing ones. 6e only looks at the dice in the pool itself,
+    // so dice added afterwards by rerolls or Edge can't water a glitch down.
+    pub fn get_glitch(&self) -> bool {
+        // Year Zero pools have banes and Genesys dice despair instead
+        if self
+            .results
+            .iter()
+            .any(|result| result.kind.is_some() || result.symbol.is_some())
+        {
+            return false;
+        }
+        let pool: Vec<&Result> = self
+            .results
+            .iter()
+            .filter(|result| !result.dropped && (self.rules != Rules::Sr6 || !result.extra))
+            .collect();
+        let fails = pool.iter().filter(|result| result.critfail).count();
+        fails * 2 > pool.len()
+    }
+
+    // Edge: +1 to a single die, choosing one that becomes a hit or else a one.
+    pub fn boost_die(&mut self, success: u16) {
+        let index = self
+            .results
+            .iter()
+            .position(|result| !result.dropped && !result.hit && result.value + 1 >= success)
+            .or_else(|| {
+                self.results
+                    .iter()
+                    .position(|result| !result.dropped && result.critfail)
+            });
+        let Some(index) = index else {
+            self.add_note("Edge: no die worth +1".to_string());
+            return;
+        };
+        let result = &mut self.results[index];
+        let before = result.value;
+        result.value += 1;
+        result.critfail = false;
+        let became_hit = !result.hit && result.value >= success;
+        result.hit |= became_hit;
+        let after = result.value;
+        self.total += 1;
+        self.hits += if became_hit { 1 } else { 0 };
+        self.add_note(format!("Edge: +1 to a die ({} -> {})", before, after));
+    }
+
+    // Edge: reroll a single die that missed, a one if there is one.
+    pub fn reroll_die(&mut self, success: u16) {
+        let index = self
+            .results
+            .iter()
+            .position(|result| !result.dropped && result.critfail)
+            .or_else(|| {
+                self.results
+                    .iter()
+                    .position(|result| !result.dropped && !result.hit)
+            });
+        let Some(index) = index else {
+            self.add_note("Edge: no missed die to reroll".to_string());
+            return;
+        };
+        let result = &mut self.results[index];
+        let before = result.value;
+        result.value = 1 + result.modifier + rand::random::<u16>() % result.sides;
+        result.critfail = result.value == 1;
+        result.hit = result.value >= success;
+        let after = result.value;
+        let became_hit = result.hit;
+        self.total = self.total - before + after;
+        self.hits += if became_hit { 1 } else { 0 };
+        self.add_note(format!("Edge: rerolled a die ({} -> {})", before, after));
+    }
+
+    // Edge: add Edge dice to the roll, with every highest face exploding.
+    pub fn push_edge(&mut self, edge: u16, sides: u16, success: u16) {
+        if edge == 0 {
+            self.add_note("Edge: no Edge dice to add (set them with --edge)".to_string());
+            return;
+        }
+        let mut remaining = edge;
+        while remaining > 0 {
+            remaining -= 1;
+            let value = 1 + rand::random::<u16>() % sides;
+            if value == sides {
+                remaining += 1;
             }
+            self.add_result(Result {
+                critfail: value == 1,
+                crit: false,
+                hit: value >= success,
+                value,
+                sides,
+                modifier: 0,
+                extra: true,
+                dropped: false,
+                ace: false,
+                kind: None,
+                symbol: None,
+            });
         }
-        false
+        self.add_note(format!("Edge: added {} exploding dice", edge));
     }
 
     pub fn print(&self, verbose: bool, f: &mut std::fmt::Formatter<'_>) {
+        if let Some(label) = &self.label {
+            f.write_str(format!("{}\n", label.as_str().bold()).as_str())
+                .ok();
+        }
+        for (label, delta) in &self.adjustments {
+            f.write_str(format!("{:<16}{:+}\n", format!("{}:", label), delta).as_str())
+                .ok();
+        }
+        if !self.adjustments.is_empty() {
+            f.write_str(format!("Pool:\t\t{} dice\n", self.pool).as_str())
+                .ok();
+        }
+        match self.empty {
+            Some(EmptyPool::AutoFail) => {
+                f.write_str(format!("{}\n", EmptyPool::AutoFail.get_label().dark_red()).as_str())
+                    .ok();
+            }
+            Some(rule) => {
+                f.write_str(format!("{}\n", rule.get_label().dark_yellow()).as_str())
+                    .ok();
+            }
+            None => {}
+        }
         if self.get_results().is_empty() && !self.summaries.is_empty() {
             f.write_str(format!("{}\n", chrono::Local::now()).as_str())
                 .ok();
@@ -82,11 +495,34 @@ impl Summary {
             if verbose {
                 self.print_dice(f)
             };
-            f.write_str(format!("Hits:\t\t{}\n", self.hits).as_str())
-                .ok();
-            f.write_str(format!("Total (+{}):\t{}\n", self.total_modifier, self.total).as_str())
-                .ok();
+            // narrative dice are read by their symbols, which the notes already count
+            let symbols = self.results.iter().any(|result| result.symbol.is_some());
+            if !symbols && !self.no_hits {
+                match self.limit {
+                    Some((limit, rolled)) if rolled > limit => f
+                        .write_str(
+                            format!(
+                                "Hits:\t\t{} (limit {}, rolled {})\n",
+                                self.hits, limit, rolled
+                            )
+                            .as_str(),
+                        )
+                        .ok(),
+                    _ => f
+                        .write_str(format!("Hits:\t\t{}\n", self.hits).as_str())
+                        .ok(),
+                };
+            }
+            if !symbols && !self.no_total {
+                let modifier = self.total_modifier as i32 - self.penalty as i32;
+                f.write_str(format!("Total ({:+}):\t{}\n", modifier, self.total).as_str())
+                    .ok();
+            }
             self.print_glitch(f);
+            for note in &self.notes {
+                f.write_str(format!("{}\n", note.as_str().dark_yellow()).as_str())
+                    .ok();
+            }
             f.write_str(
                 "____________________________________\n"
                     .to_string()
@@ -101,14 +537,35 @@ impl Summary {
 
     fn print_dice(&self, f: &mut std::fmt::Formatter<'_>) {
         for result in self.get_results() {
-            if result.modifier != 0 {
+            if result.ace {
+                f.write_str(format!("  + d{}\t", result.sides).as_str())
+                    .ok();
+            } else if let Some(die) = result.symbol {
+                let face = die.get_faces()[result.value as usize - 1];
+                f.write_str(
+                    format!(
+                        " {:<22}\t{}\n",
+                        format!("{} d{}", die.get_label(), result.sides),
+                        face.get_label()
+                    )
+                    .as_str(),
+                )
+                .ok();
+                continue;
+            } else if let Some(kind) = result.kind {
+                f.write_str(format!(" {} d{}\t", kind.get_label(), result.sides).as_str())
+                    .ok();
+            } else if result.modifier != 0 {
                 f.write_str(format!(" d{} (+{})\t", result.sides, result.modifier).as_str())
                     .ok();
             } else {
                 f.write_str(format!(" d{}\t\t", result.sides).as_str()).ok();
             }
             f.write_char('\t').ok();
-            if result.hit && !result.crit {
+            if result.dropped {
+                f.write_str(format!("{} (dropped)", result.value.to_string().dim()).as_str())
+                    .ok();
+            } else if result.hit && !result.crit {
                 f.write_str(format!("{}", result.value.to_string().green()).as_str())
                     .ok();
             } else if result.crit {
@@ -125,8 +582,10 @@ impl Summary {
     }
 
     fn print_glitch(&self, f: &mut std::fmt::Formatter<'_>) {
-        if self.get_glitch() {
-            if self.hits == 0 {
+        // glitches are Shadowrun's, a Blades or 40k roll or one read by bands has none
+        if !matches!(self.rules, Rules::Blades | Rules::Wh40k) && !self.banded && self.get_glitch()
+        {
+            if self.get_critical_glitch() {
                 f.write_str(format!("{}", "Critical glitch!\n".dark_red()).as_str())
                     .ok();
                 // drawterm::print_red("Critical glitch!\n".to_string());
@@ -198,14 +657,107 @@ impl Default for Die {
     }
 }
 
+// What happens when modifiers leave a pool with no dice at all.
+#[derive(Clone, Copy, PartialEq)]
+pub enum EmptyPool {
+    // a single die that only hits on its highest face and glitches on a one
+    ChanceDie,
+    AutoFail,
+    MinimumOne,
+    // Blades in the Dark: roll two dice and keep the lowest
+    LowestOfTwo,
+}
+
+impl EmptyPool {
+    pub fn from(arg: &str) -> Option<Self> {
+        match arg {
+            "chance" => Some(Self::ChanceDie),
+            "fail" => Some(Self::AutoFail),
+            "one" => Some(Self::MinimumOne),
+            "lowest" => Some(Self::LowestOfTwo),
+            _ => None,
+        }
+    }
+
+    pub fn get_label(&self) -> &str {
+        match self {
+            Self::ChanceDie => "Chance die",
+            Self::AutoFail => "Automatic failure",
+            Self::MinimumOne => "Minimum one die",
+            Self::LowestOfTwo => "Zero dice: lowest of two",
+        }
+    }
+}
+
+// Which die of each pair counts, for advantage and disadvantage.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Keep {
+    Highest,
+    Lowest,
+}
+
 pub struct DiceGroup {
     pub dice: Vec<Die>,
     hit: u16,
+    sides: u16,
+    modifier: u16,
+    // taken off the total, like the -1 in 2d6-1, without ever taking it below zero
+    penalty: u16,
+    adjustments: Vec<(String, i16)>,
+    empty: Option<EmptyPool>,
+    // every die is rolled twice and only one of the pair is kept
+    keep: Option<Keep>,
+    // Savage Worlds: an acing trait die rolled alongside an acing d6, keeping the better
+    wild: bool,
+    // roll-and-keep (7k4): exploding d10s of which only the highest few count
+    roll_keep: Option<u16>,
+    // Year Zero: d6s split into base, skill and gear pools
+    yze: Option<Pools>,
+    // Genesys: narrative dice read by their symbols rather than their numbers
+    symbol_dice: Vec<SymbolDie>,
 }
 
 impl DiceGroup {
     pub fn new(dice: Vec<Die>, hit: u16) -> Self {
-        Self { dice, hit }
+        let sides = dice.first().map(|die| die.sides).unwrap_or(0);
+        Self {
+            dice,
+            hit,
+            sides,
+            modifier: 0,
+            adjustments: vec![],
+            penalty: 0,
+            empty: None,
+            keep: None,
+            wild: false,
+            roll_keep: None,
+            yze: None,
+            symbol_dice: vec![],
+        }
+    }
+
+    // Builds a pool from an itemized ledger of dice, each entry shown with the roll.
+    pub fn from_ledger(items: &[(String, i16)], sides: u16, hit: u16) -> Self {
+        let size = items.iter().map(|(_, count)| *count as i32).sum::<i32>();
+        let die = Die {
+            crit: false,
+            sides,
+            modifier: 0,
+        };
+        Self {
+            dice: vec![die; size.max(0) as usize],
+            hit,
+            sides,
+            modifier: 0,
+            adjustments: items.to_vec(),
+            penalty: 0,
+            empty: None,
+            keep: None,
+            wild: false,
+            roll_keep: None,
+            yze: None,
+            symbol_dice: vec![],
+        }
     }
 
     pub fn from_previous(
@@ -220,10 +772,37 @@ impl DiceGroup {
     }
 
     pub fn from(dice_args: &str, crits: u16, hit: u16, no_shitty_crit: bool) -> Option<Self> {
+        // 1d20adv+5 and 1d20dis+3 roll every die twice
+        let keep = if dice_args.contains("adv") {
+            Some(Keep::Highest)
+        } else if dice_args.contains("dis") {
+            Some(Keep::Lowest)
+        } else {
+            None
+        };
+        let wild = dice_args.contains('w');
+        // 2d6-1 takes one off the total, never a die off the pool
+        let (dice_args, penalty) = match dice_args.rsplit_once('-') {
+            Some((dice, penalty)) if dice.contains('d') => (dice, penalty.parse().ok()?),
+            _ => (dice_args, 0),
+        };
+        let mut dice_args = dice_args
+            .replace("adv", "")
+            .replace("dis", "")
+            .replace('w', "");
+        // a wild die is always rolled alongside a single trait die, so d8w means 1d8w
+        if wild && dice_args.starts_with('d') {
+            dice_args.insert(0, '1');
+        }
+        let dice_args = &dice_args;
         let mut dice_vec = vec![];
+        let mut modifier = 0;
+        let sides;
         let mut crits = crits;
         if !dice_args.contains('d') {
-            return None;
+            return Self::from_yze(dice_args, hit)
+                .or_else(|| Self::from_symbols(dice_args, hit))
+                .or_else(|| Self::from_roll_keep(dice_args, hit));
         }
 
         if dice_args.contains('*') {
@@ -236,39 +815,144 @@ impl DiceGroup {
             let mut d_split = multiple_split.next().unwrap().split('d');
             let dice = d_split.next().unwrap_or("0").parse::<u16>().unwrap_or(0);
             let side = d_split.next().unwrap_or("1");
+            // a multiplier of zero (like 'x' after no hits) rolls nothing, rather than an empty pool
+            if rolls == 0 && crits == 0 {
+                return None;
+            }
+            sides = parse_side(side).0;
             if no_shitty_crit {
                 rolls -= crits;
                 crits *= 2;
             }
             for _ in 0..rolls {
-                fill_dice(dice, side, false, &mut dice_vec);
+                fill_dice(dice, side, false, &mut dice_vec, &mut modifier);
             }
             for _ in 0..crits {
-                fill_dice(dice, side, true, &mut dice_vec);
+                fill_dice(dice, side, true, &mut dice_vec, &mut modifier);
             }
         } else {
             let mut d_split = dice_args.split('d');
             let mut rolls = 1;
             let dice = d_split.next().unwrap_or("0").parse::<u16>().unwrap_or(0);
             let side = d_split.next().unwrap_or("1");
+            sides = parse_side(side).0;
             if no_shitty_crit {
                 rolls -= if crits > 0 { rolls } else { 0 };
                 crits *= 2;
             }
             for _ in 0..rolls {
-                fill_dice(dice, side, false, &mut dice_vec);
+                fill_dice(dice, side, false, &mut dice_vec, &mut modifier);
             }
             for _ in 0..crits {
-                fill_dice(dice, side, true, &mut dice_vec);
+                fill_dice(dice, side, true, &mut dice_vec, &mut modifier);
             }
         }
-        Some(Self::new(dice_vec, hit))
+        if keep.is_some() {
+            dice_vec = dice_vec
+                .into_iter()
+                .flat_map(|die| [die.clone(), die])
+                .collect();
+        }
+        let mut group = Self::new(dice_vec, hit);
+        group.sides = sides;
+        group.modifier = modifier;
+        group.keep = keep;
+        group.penalty = penalty;
+        if wild {
+            // the modifier applies to whichever die is kept
+            for die in &mut group.dice {
+                group.modifier += die.modifier;
+                die.modifier = 0;
+            }
+            group.wild = true;
+        }
+        Some(group)
     }
 
     pub fn get_count(&self) -> u16 {
         self.dice.len() as u16
     }
 
+    // 7k4+5 rolls seven exploding d10s and keeps the four highest, with 5 added to the total.
+    fn from_roll_keep(dice_args: &str, hit: u16) -> Option<Self> {
+        let (dice, rest) = dice_args.split_once('k')?;
+        let (keep, modifier) = rest.split_once('+').unwrap_or((rest, "0"));
+        let dice: u16 = dice.parse().ok()?;
+        let die = Die {
+            crit: false,
+            sides: 10,
+            modifier: 0,
+        };
+        let mut group = Self::new(vec![die; dice as usize], hit);
+        group.sides = 10;
+        group.modifier = modifier.parse().unwrap_or(0);
+        group.roll_keep = Some(keep.parse::<u16>().ok()?.min(dice));
+        Some(group)
+    }
+
+    // 4b3s2g rolls four base dice, three skill dice and two gear dice.
+    fn from_yze(dice_args: &str, hit: u16) -> Option<Self> {
+        let pools = Pools::from(dice_args).filter(|pools| pools.get_count() > 0)?;
+        let die = Die {
+            crit: false,
+            sides: 6,
+            modifier: 0,
+        };
+        let mut group = Self::new(vec![die; pools.get_count() as usize], hit);
+        group.yze = Some(pools);
+        Some(group)
+    }
+
+    // 2A1P2D1S rolls two ability, one proficiency, two difficulty and one setback die.
+    fn from_symbols(dice_args: &str, hit: u16) -> Option<Self> {
+        let symbol_dice = genesys::parse_symbol_dice(dice_args)?;
+        let dice = symbol_dice
+            .iter()
+            .map(|die| Die {
+                crit: false,
+                sides: die.get_sides(),
+                modifier: 0,
+            })
+            .collect();
+        let mut group = Self::new(dice, hit);
+        group.symbol_dice = symbol_dice;
+        Some(group)
+    }
+
+    pub fn get_symbol_dice(&self) -> &[SymbolDie] {
+        &self.symbol_dice
+    }
+
+    pub fn get_yze(&self) -> Option<Pools> {
+        self.yze
+    }
+
+    // Wounds or the empty pool rule can leave fewer dice than were to be kept.
+    pub fn get_roll_keep(&self) -> Option<u16> {
+        self.roll_keep.map(|keep| keep.min(self.get_count()))
+    }
+
+    pub fn is_wild(&self) -> bool {
+        self.wild
+    }
+
+    pub fn get_keep(&self) -> Option<Keep> {
+        self.keep
+    }
+
+    pub fn set_keep(&mut self, keep: Option<Keep>) {
+        self.keep = keep;
+    }
+
+    // The dice that count once advantage or disadvantage has dropped the other of each pair.
+    pub fn get_kept_count(&self) -> u16 {
+        match (self.keep, self.get_roll_keep()) {
+            (Some(_), _) => self.get_count() / 2,
+            (None, Some(keep)) => keep,
+            (None, None) => self.get_count(),
+        }
+    }
+
     pub fn get_hit(&self) -> u16 {
         self.hit
     }
@@ -285,9 +969,111 @@ impl DiceGroup {
         Option::Some(temp.sides)
     }
 
-    pub fn get_total_modifier(&self) -> u16 {
-        let mut temp = 0;
-        for die in &self.dice {
+    // A Shadowrun pool: plain d6s that count hits.
+    pub fn is_hit_pool(&self) -> bool {
+        self.get_sides() == Some(6)
+            && self.hit <= 6
+            && self.keep.is_none()
+            && !self.wild
+            && self.roll_keep.is_none()
+            && self.yze.is_none()
+            && self.symbol_dice.is_empty()
+    }
+
+    // Wounds are a Shadowrun rule, so only hit pools lose dice for them.
+    pub fn apply_wounds(&mut self, wounds: u16) {
+        if wounds > 0 && self.is_hit_pool() {
+            self.adjust_pool("Wound modifier", -(wounds as i16));
+        }
+    }
+
+    // Adds or removes dice from the pool, keeping a record of why so it can be shown with the roll.
+    // Groups with nothing to roll (like 'x' after no hits) are left alone.
+    pub fn adjust_pool(&mut self, label: &str, delta: i16) {
+        if self.sides == 0 {
+            return;
+        }
+        // advantage and disadvantage roll every die as a pair, so whole pairs come and go
+        let step = if self.keep.is_some() { 2 } else { 1 };
+        if delta < 0 {
+            let remove = (delta.unsigned_abs() as usize * step).min(self.dice.len());
+            self.dice.truncate(self.dice.len() - remove);
+        } else {
+            for _ in 0..delta as usize * step {
+                self.dice.push(Die {
+                    crit: false,
+                    sides: self.sides,
+                    modifier: 0,
+                });
+            }
+        }
+        self.adjustments.push((label.to_string(), delta));
+    }
+
+    // Applies the empty pool rule once every adjustment has been made.
+    pub fn resolve_empty_pool(&mut self, rule: EmptyPool) {
+        if !self.dice.is_empty() || self.sides == 0 {
+            return;
+        }
+        self.empty = Some(rule);
+        if rule == EmptyPool::LowestOfTwo {
+            let die = Die {
+                crit: false,
+                sides: self.sides,
+                modifier: 0,
+            };
+            self.dice = vec![die; 2];
+            self.keep = Some(Keep::Lowest);
+        } else if rule != EmptyPool::AutoFail {
+            let die = Die {
+                crit: false,
+                sides: self.sides,
+                modifier: 0,
+            };
+            let step = if self.keep.is_some() { 2 } else { 1 };
+            self.dice = vec![die; step];
+            if let Some(pools) = self.yze.as_mut() {
+                pools.base = 1;
+            }
+        }
+    }
+
+    pub fn get_empty_pool(&self) -> Option<EmptyPool> {
+        self.empty
+    }
+
+    pub fn is_chance(&self) -> bool {
+        self.empty == Some(EmptyPool::ChanceDie)
+    }
+
+    pub fn get_adjustments(&self) -> &[(String, i16)] {
+        &self.adjustments
+    }
+
+    // A modifier added once to the total of the roll, never to any one die.
+    pub fn set_modifier(&mut self, modifier: u16) {
+        self.modifier = modifier;
+    }
+
+    // Like the modifier, but taken off the total.
+    pub fn set_penalty(&mut self, penalty: u16) {
+        self.penalty = penalty;
+    }
+
+    // A total rolled on the dice with every result modifier applied.
+    pub fn get_total(&self, roll: u16) -> u16 {
+        (roll as i32 + self.get_net_modifier()).max(0) as u16
+    }
+
+    // Every result modifier on the group, less its penalty. Probabilities shift by this.
+    pub fn get_net_modifier(&self) -> i32 {
+        self.get_total_modifier() as i32 - self.penalty as i32
+    }
+
+    fn get_total_modifier(&self) -> u16 {
+        let step = if self.keep.is_some() { 2 } else { 1 };
+        let mut temp = self.modifier;
+        for die in self.dice.iter().step_by(step) {
             temp += die.modifier;
         }
         temp
@@ -299,20 +1085,27 @@ impl Clone for DiceGroup {
         Self {
             dice: self.dice.clone(),
             hit: self.hit,
+            sides: self.sides,
+            modifier: self.modifier,
+            adjustments: self.adjustments.clone(),
+            penalty: self.penalty,
+            empty: self.empty,
+            keep: self.keep,
+            wild: self.wild,
+            roll_keep: self.roll_keep,
+            yze: self.yze,
+            symbol_dice: self.symbol_dice.clone(),
         }
     }
 }
 
-fn fill_dice(dice: u16, side: &str, crit: bool, dice_vec: &mut Vec<Die>) {
-    let mut modifier = 0;
-    let sides;
-
-    if side.contains('+') {
-        let mut plus_split = side.split('+');
-        sides = plus_split.next().unwrap_or("1").parse::<u16>().unwrap_or(1);
-        modifier = plus_split.next().unwrap_or("0").parse::<u16>().unwrap_or(0);
-    } else {
-        sides = side.parse::<u16>().unwrap_or(1);
+// A lone die carries its modifier so hits are counted against the modified roll (d20+8 vs 14).
+// With more dice the modifier belongs to the total and goes to the group instead of a die.
+fn fill_dice(dice: u16, side: &str, crit: bool, dice_vec: &mut Vec<Die>, group_modifier: &mut u16) {
+    let (sides, mut modifier) = parse_side(side);
+    if dice > 1 {
+        *group_modifier += modifier;
+        modifier = 0;
     }
 
     for _ in 0..dice {
@@ -325,11 +1118,43 @@ fn fill_dice(dice: u16, side: &str, crit: bool, dice_vec: &mut Vec<Die>) {
     }
 }
 
+fn parse_side(side: &str) -> (u16, u16) {
+    if side.contains('+') {
+        let mut plus_split = side.split('+');
+        let sides = plus_split.next().unwrap_or("1").parse::<u16>().unwrap_or(1);
+        let modifier = plus_split.next().unwrap_or("0").parse::<u16>().unwrap_or(0);
+        (sides, modifier)
+    } else {
+        (side.parse::<u16>().unwrap_or(1), 0)
+    }
+}
+
+fn roll_aces(sides: u16) -> Vec<u16> {
+    let mut rolls = vec![];
+    loop {
+        let value = 1 + rand::random::<u16>() % sides.max(1);
+        rolls.push(value);
+        if value != sides || sides <= 1 {
+            return rolls;
+        }
+    }
+}
+
 impl Default for DiceGroup {
     fn default() -> Self {
         Self {
             dice: vec![],
             hit: u16::MAX,
+            sides: 0,
+            modifier: 0,
+            adjustments: vec![],
+            penalty: 0,
+            empty: None,
+            keep: None,
+            wild: false,
+            roll_keep: None,
+            yze: None,
+            symbol_dice: vec![],
         }
     }
 }
@@ -344,38 +1169,212 @@ pub struct Roller {
 
 impl Roller {
     pub fn from_dice_group(dice: DiceGroup, critval: u16, success: u16, reroll: u16) -> Self {
+        let mut summary = Summary::new();
+        summary.set_pool(
+            dice.get_adjustments(),
+            dice.get_count(),
+            dice.get_empty_pool(),
+        );
         Self {
             dice,
             critval,
             success,
             reroll,
-            summary: Summary::new(),
+            summary,
         }
     }
 
+    // Rolls a group counting only hits, the way opposed and resistance tests are rolled.
+    pub fn roll_group(dice: DiceGroup, success: u16) -> Summary {
+        let mut roller = Self::from_dice_group(dice, u16::MAX, success, u16::MAX);
+        roller.roll(false);
+        roller.get_summary()
+    }
+
     pub fn roll(&mut self, nsc: bool) {
-        let mut reroll = self.add_results(&self.dice.dice.to_owned(), nsc);
+        if self.dice.wild {
+            self.roll_wild();
+            self.summary.add_modifier(self.dice.modifier);
+            self.summary.subtract_modifier(self.dice.penalty);
+            return;
+        }
+        if let Some(pools) = self.dice.yze {
+            self.roll_yze(pools);
+            return;
+        }
+        if !self.dice.symbol_dice.is_empty() {
+            self.roll_symbols();
+            return;
+        }
+        if let Some(keep) = self.dice.get_roll_keep() {
+            self.roll_and_keep(keep);
+            self.summary.add_modifier(self.dice.modifier);
+            self.summary.subtract_modifier(self.dice.penalty);
+            return;
+        }
+        let mut reroll = self.add_results(&self.dice.dice.to_owned(), nsc, false);
 
         while !reroll.is_empty() {
-            reroll = self.add_results(&reroll, nsc);
+            reroll = self.add_results(&reroll, nsc, true);
         }
+        self.summary.add_modifier(self.dice.modifier);
+        self.summary.subtract_modifier(self.dice.penalty);
     }
 
-    fn add_results(&mut self, dice: &Vec<Die>, nsc: bool) -> Vec<Die> {
+    fn roll_symbols(&mut self) {
+        for die in self.dice.symbol_dice.iter().copied() {
+            let sides = die.get_sides();
+            self.summary.add_result(Result {
+                critfail: false,
+                crit: false,
+                hit: false,
+                value: 1 + rand::random::<u16>() % sides,
+                sides,
+                modifier: 0,
+                extra: false,
+                dropped: false,
+                ace: false,
+                kind: None,
+                symbol: Some(die),
+            });
+        }
+    }
+
+    // Sixes are successes on every kind of die, and ones on base and gear dice are banes.
+    fn roll_yze(&mut self, pools: Pools) {
+        for kind in Kind::ALL {
+            for _ in 0..pools.get(kind) {
+                let value = 1 + rand::random::<u16>() % 6;
+                self.summary.add_result(Result {
+                    critfail: kind.is_bane(value),
+                    crit: false,
+                    hit: value == 6,
+                    value,
+                    sides: 6,
+                    modifier: 0,
+                    extra: false,
+                    dropped: false,
+                    ace: false,
+                    kind: Some(kind),
+                    symbol: None,
+                });
+            }
+        }
+    }
+
+    // Every die explodes on a 10 and only the highest chains are kept.
+    fn roll_and_keep(&mut self, keep: u16) {
+        let chains: Vec<Vec<u16>> = self
+            .dice
+            .dice
+            .iter()
+            .map(|die| roll_aces(die.sides))
+            .collect();
+        let mut order: Vec<usize> = (0..chains.len()).collect();
+        order.sort_by_key(|i| std::cmp::Reverse(chains[*i].iter().sum::<u16>()));
+        for (i, chain) in chains.iter().enumerate() {
+            let dropped = !order[..keep as usize].contains(&i);
+            for (j, value) in chain.iter().enumerate() {
+                self.summary.add_result(Result {
+                    critfail: false,
+                    crit: false,
+                    hit: *value >= self.success,
+                    value: *value,
+                    sides: 10,
+                    modifier: 0,
+                    extra: j > 0,
+                    dropped,
+                    ace: j > 0,
+                    kind: None,
+                    symbol: None,
+                });
+            }
+        }
+    }
+
+    // The trait die and the wild die both ace on their highest face, and only the better total counts.
+    fn roll_wild(&mut self) {
+        let sides = self.dice.sides;
+        let trait_die = roll_aces(sides);
+        let wild_die = roll_aces(6);
+        let wild_wins = wild_die.iter().sum::<u16>() > trait_die.iter().sum();
+        for (rolls, sides, dropped) in [(trait_die, sides, wild_wins), (wild_die, 6, !wild_wins)] {
+            for (i, value) in rolls.into_iter().enumerate() {
+                self.summary.add_result(Result {
+                    critfail: false,
+                    crit: false,
+                    hit: false,
+                    value,
+                    sides,
+                    modifier: 0,
+                    extra: i > 0,
+                    dropped,
+                    ace: i > 0,
+                    kind: None,
+                    symbol: None,
+                });
+            }
+        }
+    }
+
+    fn add_results(&mut self, dice: &[Die], nsc: bool, extra: bool) -> Vec<Die> {
         let mut reroll_result = vec![];
-        for die in dice {
-            let num = die.roll(nsc);
+        let values: Vec<u16> = dice.iter().map(|die| die.roll(nsc)).collect();
+        for (i, die) in dice.iter().enumerate() {
+            let num = values[i];
+            let dropped = match self.dice.keep {
+                // a die left without a pair is simply kept
+                Some(keep) if !extra && (i % 2 == 1 || i + 1 < values.len()) => {
+                    let (first, other) = if i % 2 == 0 {
+                        (true, values[i + 1])
+                    } else {
+                        (false, values[i - 1])
+                    };
+                    // ties drop the second die of the pair
+                    match keep {
+                        Keep::Highest => num < other || (num == other && !first),
+                        Keep::Lowest => num > other || (num == other && !first),
+                    }
+                }
+                _ => false,
+            };
+            if dropped {
+                self.summary.add_result(Result {
+                    critfail: false,
+                    crit: false,
+                    hit: false,
+                    value: num,
+                    sides: die.sides,
+                    modifier: die.modifier,
+                    extra,
+                    dropped,
+                    ace: false,
+                    kind: None,
+                    symbol: None,
+                });
+                continue;
+            }
             let crit = num - die.modifier == die.sides && num - die.modifier == self.critval;
             if num >= self.reroll {
                 reroll_result.push(die.clone());
             }
+            let hit = if self.dice.is_chance() {
+                num - die.modifier == die.sides
+            } else {
+                num >= self.success
+            };
             self.summary.add_result(Result {
                 critfail: num == 1,
                 crit,
-                hit: num >= self.success,
+                hit,
                 value: num,
                 sides: die.sides,
                 modifier: die.modifier,
+                extra,
+                dropped,
+                ace: false,
+                kind: None,
+                symbol: None,
             });
         }
         reroll_result
@@ -0,0 +1,120 @@
+use crossterm::style::Stylize;
+
+// The rule set used to read a roll. Shadowrun 5e is what everything did before modes existed.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Rules {
+    Sr5,
+    Sr6,
+    Pf2e,
+    Blades,
+    // set by the 40k command, where a one is only a failed roll
+    Wh40k,
+}
+
+impl Rules {
+    pub fn from(arg: &str) -> Option<Self> {
+        match arg {
+            "sr5" => Some(Self::Sr5),
+            "sr6" => Some(Self::Sr6),
+            "pf2e" => Some(Self::Pf2e),
+            "blades" => Some(Self::Blades),
+            _ => None,
+        }
+    }
+}
+
+// In 6e a difference of 4 or more between Attack Rating and Defense Rating earns the
+// side with the higher rating a point of Edge.
+pub fn get_edge_award(attack_rating: u16, defense_rating: u16) -> String {
+    if attack_rating >= defense_rating + 4 {
+        format!(
+            "Attack Rating {} vs Defense Rating {}: {}",
+            attack_rating,
+            defense_rating,
+            "attacker gains 1 Edge".green()
+        )
+    } else if defense_rating >= attack_rating + 4 {
+        format!(
+            "Attack Rating {} vs Defense Rating {}: {}",
+            attack_rating,
+            defense_rating,
+            "defender gains 1 Edge".dark_yellow()
+        )
+    } else {
+        format!(
+            "Attack Rating {} vs Defense Rating {}: no Edge awarded",
+            attack_rating, defense_rating
+        )
+    }
+}
+
+// Pathfinder 2e degrees of success, worst first.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum Degree {
+    CriticalFailure,
+    Failure,
+    Success,
+    CriticalSuccess,
+}
+
+impl Degree {
+    pub const ALL: [Degree; 4] = [
+        Degree::CriticalSuccess,
+        Degree::Success,
+        Degree::Failure,
+        Degree::CriticalFailure,
+    ];
+
+    // Beating the DC by 10 is a critical success and missing it by 10 a critical failure.
+    // A natural 20 then improves the degree by a step and a natural 1 worsens it.
+    pub fn from(total: u16, natural: Option<u16>, dc: u16) -> Self {
+        let degree = if total >= dc + 10 {
+            Self::CriticalSuccess
+        } else if total >= dc {
+            Self::Success
+        } else if total + 10 > dc {
+            Self::Failure
+        } else {
+            Self::CriticalFailure
+        };
+        match natural {
+            Some(20) => degree.step(1),
+            Some(1) => degree.step(-1),
+            _ => degree,
+        }
+    }
+
+    fn step(self, by: i8) -> Self {
+        match (self as i8 + by).clamp(0, 3) {
+            0 => Self::CriticalFailure,
+            1 => Self::Failure,
+            2 => Self::Success,
+            _ => Self::CriticalSuccess,
+        }
+    }
+
+    pub fn get_label(&self) -> &str {
+        match self {
+            Self::CriticalSuccess => "Critical success",
+            Self::Success => "Success",
+            Self::Failure => "Failure",
+            Self::CriticalFailure => "Critical failure",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degrees() {
+        assert!(Degree::from(28, None, 18) == Degree::CriticalSuccess);
+        assert!(Degree::from(18, None, 18) == Degree::Success);
+        assert!(Degree::from(9, None, 18) == Degree::Failure);
+        assert!(Degree::from(8, None, 18) == Degree::CriticalFailure);
+        assert!(Degree::from(27, Some(20), 18) == Degree::CriticalSuccess);
+        assert!(Degree::from(8, Some(1), 18) == Degree::CriticalFailure);
+        assert!(Degree::from(30, Some(1), 18) == Degree::Success);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, f64::consts::PI, io::Stdout};
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+
+use crate::{
+    components::Component,
+    probability::{Probability, Total},
+    roll::{DiceGroup, Roller},
+};
+
+// Where a thrown area weapon lands: the base dice less the net hits in metres, in a direction
+// read off the d6 scatter diagram. A 1 carries on past the target and the rest go clockwise.
+pub struct Scatter {
+    base: DiceGroup,
+    net_hits: u16,
+}
+
+impl Scatter {
+    pub fn new(base: DiceGroup, net_hits: u16) -> Self {
+        Self { base, net_hits }
+    }
+
+    // Rolls direction and distance, returning both along with the offset from the target.
+    pub fn roll(&self) -> (u16, u16, (i32, i32)) {
+        let direction = DiceGroup::from("1d6", 0, u16::MAX, false).unwrap_or_default();
+        let direction = Roller::roll_group(direction, u16::MAX).total;
+        let rolled = Roller::roll_group(self.base.clone(), u16::MAX).total;
+        let distance = rolled.saturating_sub(self.net_hits);
+        (direction, rolled, get_offset(direction, distance))
+    }
+
+    pub fn get_distances(&self) -> HashMap<u16, f64> {
+        let totals = Total::from_dice(&self.base);
+        let mut distances = HashMap::new();
+        // the data is already keyed by total, with the modifiers and any penalty applied
+        for (total, percent) in totals.to_data() {
+            *distances
+                .entry(total.saturating_sub(self.net_hits))
+                .or_insert(0.) += percent as f64 / 100.;
+        }
+        distances
+    }
+
+    // The chance of landing on every square metre around the target.
+    pub fn get_distribution(&self) -> HashMap<(i32, i32), f64> {
+        let mut cells = HashMap::new();
+        for (distance, p) in self.get_distances() {
+            for direction in 1..=6 {
+                *cells.entry(get_offset(direction, distance)).or_insert(0.) += p / 6.;
+            }
+        }
+        cells
+    }
+
+    pub fn get_max_distance(&self) -> u16 {
+        let sides = self.base.get_sides().unwrap_or(0);
+        self.base
+            .get_total(self.base.get_count() * sides)
+            .saturating_sub(self.net_hits)
+    }
+}
+
+pub fn get_direction_label(direction: u16) -> &'static str {
+    match direction {
+        1 => "long",
+        2 => "long and right",
+        3 => "short and right",
+        4 => "back toward the thrower",
+        5 => "short and left",
+        _ => "long and left",
+    }
+}
+
+// Directions sit 60 degrees apart with up pointing away from the thrower.
+fn get_offset(direction: u16, distance: u16) -> (i32, i32) {
+    let angle = (direction.max(1) - 1) as f64 * PI / 3.;
+    let distance = distance as f64;
+    (
+        (distance * angle.sin()).round() as i32,
+        (-distance * angle.cos()).round() as i32,
+    )
+}
+
+// A map of the ground around the target, one cell per metre, with the thrower below it.
+pub struct ScatterGrid {
+    text: String,
+}
+
+impl ScatterGrid {
+    pub fn landing(radius: u16, landing: (i32, i32)) -> Self {
+        let text = get_grid(radius, |cell| {
+            if cell == landing {
+                format!("{}", "* ".bold().dark_red())
+            } else if cell == (0, 0) {
+                "X ".to_string()
+            } else {
+                "· ".to_string()
+            }
+        });
+        Self { text }
+    }
+
+    pub fn heatmap(cells: &HashMap<(i32, i32), f64>, radius: u16) -> Self {
+        let max = cells.values().fold(0., |max: f64, p| max.max(*p));
+        let text = get_grid(radius, |cell| match cells.get(&cell) {
+            Some(p) if *p > 0. => {
+                let shade = ['░', '▒', '▓', '█'][((p / max * 4.).ceil() as usize).clamp(1, 4) - 1];
+                format!("{}{}", shade, shade)
+            }
+            _ if cell == (0, 0) => "X ".to_string(),
+            _ => "· ".to_string(),
+        });
+        Self { text }
+    }
+}
+
+fn get_grid<F: Fn((i32, i32)) -> String>(radius: u16, draw: F) -> String {
+    let radius = radius as i32;
+    let mut text = String::from("\n");
+    for y in -radius..=radius {
+        for x in -radius..=radius {
+            text += &draw((x, y));
+        }
+        text += "\n";
+    }
+    text + &format!("{:>width$}\n", "thrower", width = radius as usize * 2 + 4)
+}
+
+impl Component for ScatterGrid {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+// The chance of each scatter distance and of landing within it.
+pub struct ScatterDisplay {
+    text: String,
+}
+
+impl ScatterDisplay {
+    pub fn new(scatter: &Scatter) -> Self {
+        let distances = scatter.get_distances();
+        let mut text = format!("\n{}\n", "Distance\tProbability\tWithin".bold());
+        let mut within = 0.;
+        for distance in 0..=scatter.get_max_distance() {
+            let p = *distances.get(&distance).unwrap_or(&0.);
+            within += p;
+            let line = format!(
+                "{} m\t\t{:>8.4} %\t{:>8.4} %\n",
+                distance,
+                p * 100.,
+                within * 100.
+            );
+            text += &if distance == 0 {
+                format!("{}", line.bold())
+            } else {
+                line
+            };
+        }
+        Self { text }
+    }
+}
+
+impl Component for ScatterDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scatter_distances() {
+        let base = DiceGroup::from("2d6-1", 0, u16::MAX, false).unwrap();
+        let scatter = Scatter::new(base, 2);
+        let distances = scatter.get_distances();
+        let total: f64 = distances.values().sum();
+        assert!((total - 1.).abs() < 1e-6);
+        // 2d6-1 less 2 net hits: a roll of 3 or less lands on target, and 12 lands 9 m away
+        assert!((distances[&0] - 3. / 36.).abs() < 1e-6);
+        assert!((distances[&9] - 1. / 36.).abs() < 1e-6);
+        assert_eq!(scatter.get_max_distance(), 9);
+
+        let base = DiceGroup::from("1d6+3-4", 0, u16::MAX, false).unwrap();
+        let total: f64 = Scatter::new(base, 0).get_distances().values().sum();
+        assert!((total - 1.).abs() < 1e-6);
+    }
+}
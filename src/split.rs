@@ -0,0 +1,156 @@
+use std::io::Stdout;
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+use itertools::Itertools;
+
+use crate::{
+    components::Component,
+    probability::{Hits, Probability},
+    roll::{DiceGroup, Roller, Summary},
+};
+
+// A pool split across several targets, every part rolled as a group of its own.
+pub struct Split {
+    dice: DiceGroup,
+    parts: Vec<u16>,
+}
+
+impl Split {
+    // None unless the parts add up to the whole pool.
+    pub fn new(dice: DiceGroup, parts: Vec<u16>) -> Option<Self> {
+        if parts.iter().sum::<u16>() != dice.get_count() {
+            return None;
+        }
+        Some(Self { dice, parts })
+    }
+
+    pub fn roll(&self, critval: u16, success: u16, reroll: u16, nsc: bool) -> Summary {
+        let mut split = Summary::new();
+        // wounds come off the whole pool before it is split, so they are shown once above the parts
+        split.set_pool(
+            self.dice.get_adjustments(),
+            self.dice.get_count(),
+            self.dice.get_empty_pool(),
+        );
+        let mut start = 0;
+        for (i, part) in self.parts.iter().enumerate() {
+            let dice = get_part(&self.dice, start, *part);
+            start += part;
+            let mut roller = Roller::from_dice_group(dice, critval, success, reroll);
+            roller.roll(nsc);
+            let mut summary = roller.get_summary();
+            summary.set_pool(&[], *part, None);
+            summary.set_label(&format!("Target {} ({} dice)", i + 1, part));
+            split += summary;
+        }
+        split
+    }
+}
+
+fn get_part(dice: &DiceGroup, start: u16, count: u16) -> DiceGroup {
+    let mut part = dice.clone();
+    part.dice = dice.dice[start as usize..(start + count) as usize].to_vec();
+    part
+}
+
+// Every way to deal `total` dice out to `targets` parts of at least one die each.
+fn get_splits(total: u16, targets: usize) -> Vec<Vec<u16>> {
+    if targets <= 1 {
+        return if total > 0 || targets == 0 {
+            vec![vec![total]]
+        } else {
+            vec![]
+        };
+    }
+    let mut splits = vec![];
+    for first in (1..=total.saturating_sub(targets as u16 - 1)).rev() {
+        for mut rest in get_splits(total - first, targets - 1) {
+            rest.insert(0, first);
+            splits.push(rest);
+        }
+    }
+    splits
+}
+
+// The chance of reaching each hit target for every possible split of the pool.
+pub struct SplitDisplay {
+    text: String,
+}
+
+impl SplitDisplay {
+    pub fn new(dice: &DiceGroup, targets: &[u16]) -> Self {
+        let mut text: String = dice
+            .get_adjustments()
+            .iter()
+            .map(|(label, delta)| format!("\n{}:\t\t{:+} dice", label, delta))
+            .collect();
+        text += &format!("\n{}", format!("Split\t\t{}", get_header(targets)).bold());
+        let rows: Vec<(String, Vec<f64>, f64)> = get_splits(dice.get_count(), targets.len())
+            .iter()
+            .map(|split| {
+                let chances: Vec<f64> = split
+                    .iter()
+                    .zip(targets)
+                    .map(|(part, target)| {
+                        Hits::from_dice(&get_part(dice, 0, *part)).get_probability_of_gt(*target)
+                    })
+                    .collect();
+                let all = chances.iter().product();
+                (split.iter().join("/"), chances, all)
+            })
+            .collect();
+        let best = rows.iter().map(|row| row.2).fold(0., f64::max);
+        for (split, chances, all) in rows {
+            let expected: f64 = chances.iter().sum();
+            let line = format!(
+                "{:<16}{}{:>8.4} %\t{:.2}\n",
+                split,
+                chances
+                    .iter()
+                    .map(|chance| format!("{:>8.4} %\t", chance * 100.))
+                    .join(""),
+                all * 100.,
+                expected
+            );
+            text += &if best > 0. && all == best {
+                format!("{}", line.bold().green())
+            } else {
+                line
+            };
+        }
+        Self { text }
+    }
+}
+
+fn get_header(targets: &[u16]) -> String {
+    targets
+        .iter()
+        .map(|target| format!("{} hits\t", target))
+        .join("\t")
+        + "\tAll\t\tTargets met\n"
+}
+
+impl Component for SplitDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splits() {
+        let splits = get_splits(12, 2);
+        assert_eq!(splits.len(), 11);
+        assert_eq!(splits[0], vec![11, 1]);
+        assert!(splits.iter().all(|split| split.iter().sum::<u16>() == 12));
+        assert_eq!(get_splits(4, 3).len(), 3);
+        assert!(get_splits(2, 3).is_empty());
+    }
+}
@@ -0,0 +1,74 @@
+// per-character values that need to survive between runs of the roller
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+pub struct State {
+    character: String,
+    values: BTreeMap<String, String>,
+}
+
+impl State {
+    pub fn load(character: &str) -> Self {
+        let mut values = BTreeMap::new();
+        let path = get_path(character);
+        if let Ok(contents) = fs::read_to_string(path) {
+            for line in contents.lines() {
+                if let Some((key, value)) = line.split_once('=') {
+                    values.insert(key.trim().to_string(), value.trim().to_string());
+                }
+            }
+        }
+        Self {
+            character: character.to_string(),
+            values,
+        }
+    }
+
+    pub fn get_character(&self) -> &str {
+        &self.character
+    }
+
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.values.get(key)?.parse().ok()
+    }
+
+    pub fn set<T: ToString>(&mut self, key: &str, value: T) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = get_path(&self.character);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        for (key, value) in &self.values {
+            writeln!(file, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+fn get_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("ROLL_HOME") {
+        PathBuf::from(dir)
+    } else if let Some(home) = env::var_os("HOME") {
+        PathBuf::from(home).join(".roll")
+    } else {
+        PathBuf::from(".roll")
+    }
+}
+
+fn get_path(character: &str) -> PathBuf {
+    let name: String = character
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    get_dir().join(format!("{}.state", name))
+}
@@ -0,0 +1,207 @@
+use std::{collections::HashMap, io::Stdout};
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+use itertools::Itertools;
+
+use crate::{
+    components::Component,
+    probability::{self, Polynomial},
+    roll::{DiceGroup, Roller, Summary},
+};
+
+// Gives up on an array minimum that the dice can hardly ever reach.
+const MAX_ARRAYS: u16 = 1000;
+
+// An ability score method like 4d6dl1x6: roll 4d6, drop the lowest one, six times over.
+pub struct Method {
+    dice: u16,
+    sides: u16,
+    drop: u16,
+    scores: u16,
+}
+
+impl Method {
+    pub fn from(arg: &str) -> Option<Self> {
+        let (score, scores) = match arg.split_once('x') {
+            Some((score, scores)) => (score, scores.parse().ok()?),
+            None => (arg, 6),
+        };
+        let (dice, drop) = match score.split_once("dl") {
+            Some((dice, drop)) => (dice, drop.parse().ok()?),
+            None => (score, 0),
+        };
+        let (count, sides) = dice.split_once('d')?;
+        let dice: u16 = count.parse().ok()?;
+        let sides = sides.parse().ok()?;
+        if dice == 0 || drop >= dice {
+            return None;
+        }
+        Some(Self {
+            dice,
+            sides,
+            drop,
+            scores,
+        })
+    }
+
+    // Rolls whole arrays until one reaches the minimum total, returning it and how many were thrown out.
+    pub fn roll(&self, min_total: u16) -> (Summary, Vec<u16>, u16) {
+        let mut rerolls = 0;
+        loop {
+            let mut array = Summary::new();
+            let mut scores = vec![];
+            for i in 1..=self.scores {
+                let dice =
+                    DiceGroup::from(&format!("{}d{}", self.dice, self.sides), 0, u16::MAX, false)
+                        .unwrap_or_default();
+                let mut score = Roller::roll_group(dice, u16::MAX);
+                score.drop_lowest(self.drop);
+                score.hide_hits();
+                score.set_label(&format!("Score {}", i));
+                scores.push(score.total);
+                array += score;
+            }
+            if scores.iter().sum::<u16>() >= min_total || rerolls >= MAX_ARRAYS {
+                return (array, scores, rerolls);
+            }
+            rerolls += 1;
+        }
+    }
+
+    pub fn get_score_distribution(&self) -> Polynomial {
+        probability::get_keep_highest(self.dice, self.sides, self.dice - self.drop)
+    }
+
+    // The chance of every (total, point buy) pair for a whole array.
+    pub fn get_array_distribution(&self) -> HashMap<(u16, i32), f64> {
+        let score = self.get_score_distribution();
+        let mut arrays: HashMap<(u16, i32), f64> = HashMap::new();
+        arrays.insert((0, 0), 1.);
+        for _ in 0..self.scores {
+            let mut next = HashMap::new();
+            for ((total, points), p) in &arrays {
+                for (value, q) in score.get_coefficients() {
+                    *next
+                        .entry((total + value, points + get_point_cost(*value)))
+                        .or_insert(0.) += p * q;
+                }
+            }
+            arrays = next;
+        }
+        arrays
+    }
+}
+
+// 5e point buy costs, extended past 8 and 15 the way most tables do.
+pub fn get_point_cost(score: u16) -> i32 {
+    match score {
+        0..=3 => -9,
+        4 => -6,
+        5 => -4,
+        6 => -2,
+        7 => -1,
+        8..=13 => score as i32 - 8,
+        14 => 7,
+        15 => 9,
+        16 => 12,
+        17 => 15,
+        _ => 19,
+    }
+}
+
+pub fn get_array_line(scores: &[u16]) -> String {
+    format!(
+        "Array:\t\t{} (total {}, point buy {})",
+        scores.iter().join(", "),
+        scores.iter().sum::<u16>(),
+        scores
+            .iter()
+            .map(|score| get_point_cost(*score))
+            .sum::<i32>()
+    )
+}
+
+// The point buy an array is worth once arrays under the minimum total are rerolled.
+pub struct PointBuyDisplay {
+    text: String,
+}
+
+impl PointBuyDisplay {
+    pub fn new(method: &Method, min_total: u16) -> Self {
+        let arrays = method.get_array_distribution();
+        let kept: f64 = arrays
+            .iter()
+            .filter(|((total, _), _)| *total >= min_total)
+            .map(|(_, p)| p)
+            .sum();
+        let mut points: HashMap<i32, f64> = HashMap::new();
+        for ((total, cost), p) in &arrays {
+            if *total >= min_total && kept > 0. {
+                *points.entry(*cost).or_insert(0.) += p / kept;
+            }
+        }
+        let expected: f64 = points.iter().map(|(cost, p)| *cost as f64 * p).sum();
+
+        let mut text = format!(
+            "\nProbability an array totals {} or more:\t{} %\nExpected point buy:\t\t\t{}\n",
+            min_total,
+            format!("{:>8.4}", kept * 100.).bold(),
+            format!("{:.2}", expected).bold()
+        );
+        text += &format!("\n{}\n", "Point buy\tProbability\tAt least".bold());
+        let mut at_least = 1.;
+        for (cost, p) in points.iter().sorted_by_key(|(cost, _)| **cost) {
+            if *p >= 0.0005 {
+                let line = format!(
+                    "{}\t\t{:>8.4} %\t{:>8.4} %\n",
+                    cost,
+                    p * 100.,
+                    at_least * 100.
+                );
+                // 27 points is the standard point buy, the fair line to compare against
+                text += &if *cost == 27 {
+                    format!("{}", line.bold())
+                } else {
+                    line
+                };
+            }
+            at_least -= p;
+        }
+        Self { text }
+    }
+}
+
+impl Component for PointBuyDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_methods() {
+        assert!(Method::from("4d6dl1x6").is_some());
+        assert!(Method::from("4d6dl4").is_none());
+        assert!(Method::from("0d6").is_none());
+        assert_eq!(get_point_cost(8), 0);
+        assert_eq!(get_point_cost(15), 9);
+
+        // a single 3d6 score: 18 costs 19 points one time in 216
+        let arrays = Method::from("3d6x1").unwrap().get_array_distribution();
+        let total: f64 = arrays.values().sum();
+        assert!((total - 1.).abs() < 1e-12);
+        assert!((arrays[&(18, 19)] - 1. / 216.).abs() < 1e-12);
+        assert!((arrays[&(3, -9)] - 1. / 216.).abs() < 1e-12);
+
+        let arrays = Method::from("4d6dl1").unwrap().get_array_distribution();
+        let total: f64 = arrays.values().sum();
+        assert!((total - 1.).abs() < 1e-9);
+    }
+}
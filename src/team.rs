@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use crate::{
+    probability::{choose, Hits, Probability},
+    roll::{DiceGroup, EmptyPool, Roller, Summary},
+};
+
+// A teamwork test: every helper's hits become extra dice for the leader, and each helper
+// with at least one hit raises the leader's limit by one. A helper who critically glitches
+// adds nothing and costs the leader a die.
+pub struct Team {
+    leader: DiceGroup,
+    helpers: Vec<DiceGroup>,
+    limit: Option<u16>,
+    empty_pool: EmptyPool,
+}
+
+impl Team {
+    pub fn new(
+        leader: DiceGroup,
+        helpers: Vec<DiceGroup>,
+        limit: Option<u16>,
+        empty_pool: EmptyPool,
+    ) -> Self {
+        Self {
+            leader,
+            helpers,
+            limit,
+            empty_pool,
+        }
+    }
+
+    pub fn roll(&self, critval: u16, success: u16, reroll: u16, nsc: bool) -> Summary {
+        let mut team = Summary::new();
+        let mut leader = self.leader.clone();
+        let mut limit = self.limit;
+        for (i, helper) in self.helpers.iter().enumerate() {
+            let mut roller = Roller::from_dice_group(helper.clone(), critval, success, reroll);
+            roller.roll(nsc);
+            let mut summary = roller.get_summary();
+            let label = format!("Helper {}", i + 1);
+            summary.set_label(&label);
+            if summary.get_critical_glitch() {
+                leader.adjust_pool(&format!("{} critical glitch", label), -1);
+            } else if summary.hits > 0 {
+                leader.adjust_pool(&label, summary.hits as i16);
+                limit = limit.map(|l| l + 1);
+            }
+            team += summary;
+        }
+        leader.resolve_empty_pool(self.empty_pool);
+        let mut roller = Roller::from_dice_group(leader, critval, success, reroll);
+        roller.roll(nsc);
+        let mut summary = roller.get_summary();
+        summary.set_label("Leader");
+        if let Some(limit) = limit {
+            summary.apply_limit(limit);
+        }
+        team += summary;
+        team
+    }
+
+    // Exact distribution of the leader's (limited) hits over every way the helpers can roll.
+    pub fn get_hits(&self) -> Hits {
+        let mut pools: HashMap<(i32, u16), f64> = HashMap::new();
+        pools.insert((self.leader.get_count() as i32, 0), 1.);
+        for helper in &self.helpers {
+            let mut next = HashMap::new();
+            for ((pool, raises), p) in &pools {
+                for (dice, raise, q) in get_helper_outcomes(helper) {
+                    *next
+                        .entry((pool + dice as i32, raises + raise as u16))
+                        .or_insert(0.) += p * q;
+                }
+            }
+            pools = next;
+        }
+
+        let mut data = HashMap::new();
+        for ((pool, raises), p) in pools {
+            let mut dice = self.leader.clone();
+            dice.adjust_pool("", (pool.max(0) - self.leader.get_count() as i32) as i16);
+            dice.resolve_empty_pool(self.empty_pool);
+            let hits = Hits::from_dice(&dice);
+            for x in 0..=dice.get_count() {
+                let capped = match self.limit {
+                    Some(limit) => x.min(limit + raises),
+                    None => x,
+                };
+                *data.entry(capped).or_insert(0.) += p * hits.get_probability_of(x);
+            }
+        }
+        Hits::from_data(data)
+    }
+
+    pub fn get_helper_critical_glitch(&self) -> f64 {
+        1. - self
+            .helpers
+            .iter()
+            .map(|helper| 1. - get_critical_glitch(helper))
+            .product::<f64>()
+    }
+}
+
+// Each way a helper can turn out: dice handed to the leader, whether the limit goes up, and how likely it is.
+fn get_helper_outcomes(helper: &DiceGroup) -> Vec<(i16, bool, f64)> {
+    let hits = Hits::from_dice(helper);
+    let critical = get_critical_glitch(helper);
+    let mut outcomes = vec![
+        (0, false, hits.get_probability_of(0) - critical),
+        (-1, false, critical),
+    ];
+    for x in 1..=helper.get_count() {
+        outcomes.push((x as i16, true, hits.get_probability_of(x)));
+    }
+    outcomes
+}
+
+// No hits with more than half the dice showing ones.
+fn get_critical_glitch(dice: &DiceGroup) -> f64 {
+    let sides = dice.get_sides().unwrap_or(1) as f64;
+    let hit = dice.get_hit().min(dice.get_sides().unwrap_or(1) + 1);
+    if hit <= 1 {
+        return 0.;
+    }
+    let n = dice.get_count();
+    let one = 1. / sides;
+    let other = (hit - 2) as f64 / sides;
+    (0..=n)
+        .filter(|ones| ones * 2 > n)
+        .map(|ones| choose(n, ones) * one.powi(ones as i32) * other.powi((n - ones) as i32))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_pool(dice: &str) -> DiceGroup {
+        DiceGroup::from(dice, 0, 5, false).unwrap()
+    }
+
+    #[test]
+    fn test_team_hits() {
+        let team = Team::new(
+            get_pool("10d6"),
+            vec![get_pool("6d6"), get_pool("5d6")],
+            Some(5),
+            EmptyPool::AutoFail,
+        );
+        let hits = team.get_hits();
+        let total: f64 = (0..=21).map(|x| hits.get_probability_of(x)).sum();
+        assert!((total - 1.).abs() < 1e-9);
+        assert!(hits.get_probability_of(0) < 0.01);
+        // the limit of 5 goes up by one for each helper with a hit
+        assert!(hits.get_probability_of(7) > 0.);
+        assert_eq!(hits.get_probability_of(8), 0.);
+        let scored = |_| {
+            team.roll(u16::MAX, 5, u16::MAX, false)
+                .to_string()
+                .lines()
+                .any(|line| line.starts_with("Hits:") && line != "Hits:\t\t0")
+        };
+        assert!((0..10).any(scored));
+
+        // more than half the dice showing ones, with the rest between the ones and the hits
+        assert!((get_critical_glitch(&get_pool("1d6")) - 1. / 6.).abs() < 1e-12);
+        assert!((get_critical_glitch(&get_pool("2d6")) - 1. / 36.).abs() < 1e-12);
+        assert!((get_critical_glitch(&get_pool("3d6")) - 10. / 216.).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,447 @@
+use std::{collections::HashMap, io::Stdout};
+
+use crossterm::{
+    queue,
+    style::{Print, Stylize},
+};
+
+use crate::{
+    components::Component,
+    probability::{self, Polynomial},
+    roll::{DiceGroup, Roller, Summary},
+    rules::Rules,
+};
+
+// Which failed dice a reroll picks up.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Reroll {
+    Ones,
+    Fails,
+}
+
+impl Reroll {
+    pub fn from(arg: &str) -> Option<Self> {
+        match arg {
+            "ones" => Some(Self::Ones),
+            "fails" => Some(Self::Fails),
+            _ => None,
+        }
+    }
+
+    // The chance a d6 passes at target+ with this reroll.
+    pub fn get_chance(reroll: Option<Self>, target: u16) -> f64 {
+        let p = get_d6_chance(target);
+        match reroll {
+            Some(Self::Ones) => p + p / 6.,
+            Some(Self::Fails) => p + p * (1. - p),
+            None => p,
+        }
+    }
+}
+
+// A characteristic like 3, D6, D3+1 or 2D6, as dice the roller understands.
+pub fn parse_characteristic(arg: &str) -> Option<DiceGroup> {
+    let arg = arg.to_lowercase();
+    if let Ok(value) = arg.parse::<u16>() {
+        let mut dice = DiceGroup::new(vec![], u16::MAX);
+        dice.set_modifier(value);
+        return Some(dice);
+    }
+    let arg = if arg.starts_with('d') {
+        format!("1{}", arg)
+    } else {
+        arg
+    };
+    DiceGroup::from(&arg, 0, u16::MAX, false)
+}
+
+// The exact distribution of a characteristic's value.
+pub fn get_distribution(dice: &DiceGroup) -> Polynomial {
+    let sides = dice.get_sides().unwrap_or(1).max(1);
+    let mut die = Polynomial::new();
+    for face in 1..=sides {
+        die.set_coefficient(face, 1. / sides as f64);
+    }
+    let mut result = Polynomial::new();
+    result.set_coefficient(0, 1.);
+    for _ in 0..dice.get_count() {
+        result *= die.clone();
+    }
+    probability::apply_modifiers(&result, dice)
+}
+
+// A 1 always fails and a 6 always passes, whatever the target.
+fn get_d6_chance(target: u16) -> f64 {
+    (7 - target.clamp(2, 7)) as f64 / 6.
+}
+
+// The wound roll needed for a Strength against a Toughness.
+pub fn get_wound_target(strength: u16, toughness: u16) -> u16 {
+    if strength >= toughness * 2 {
+        2
+    } else if strength > toughness {
+        3
+    } else if strength == toughness {
+        4
+    } else if strength * 2 > toughness {
+        5
+    } else {
+        6
+    }
+}
+
+// One unit's attacks against another: hit, wound, save and damage, each stage rolling only
+// the dice that got through the one before.
+pub struct Sequence {
+    pub attacks: DiceGroup,
+    pub skill: u16,
+    pub strength: u16,
+    pub toughness: u16,
+    pub save: u16,
+    pub ap: u16,
+    pub invuln: Option<u16>,
+    pub damage: DiceGroup,
+    pub wounds: u16,
+    pub models: Option<u16>,
+    pub fnp: Option<u16>,
+    pub reroll_hits: Option<Reroll>,
+    pub reroll_wounds: Option<Reroll>,
+}
+
+impl Sequence {
+    // AP worsens the armour save, but never an invulnerable save. 7+ can't be saved at all.
+    pub fn get_save_target(&self) -> u16 {
+        let armour = self.save + self.ap;
+        self.invuln
+            .map_or(armour, |invuln| armour.min(invuln))
+            .min(7)
+    }
+
+    // The chance a single attack gets through to damage.
+    pub fn get_unsaved_chance(&self) -> f64 {
+        Reroll::get_chance(self.reroll_hits, self.skill)
+            * Reroll::get_chance(
+                self.reroll_wounds,
+                get_wound_target(self.strength, self.toughness),
+            )
+            * (1. - get_d6_chance(self.get_save_target()))
+    }
+
+    // Rolls a stage and any rerolls, returning the rolls and how many dice passed.
+    fn roll_stage(
+        &self,
+        label: &str,
+        dice: u16,
+        target: u16,
+        reroll: Option<Reroll>,
+    ) -> (Summary, u16) {
+        let mut stage = roll_d6s(dice, target);
+        stage.set_label(label);
+        let mut passed = stage.hits;
+        let again = match reroll {
+            Some(Reroll::Ones) => stage
+                .get_results()
+                .iter()
+                .filter(|result| result.value == 1)
+                .count() as u16,
+            Some(Reroll::Fails) => dice - passed,
+            None => 0,
+        };
+        if again > 0 {
+            let mut rerolls = roll_d6s(again, target);
+            rerolls.set_label(&format!("{} rerolls", label));
+            passed += rerolls.hits;
+            stage += rerolls;
+        }
+        (stage, passed)
+    }
+
+    // Rolls the whole sequence, returning the rolls with the damage dealt and models slain.
+    pub fn roll(&self) -> (Summary, u16, u16) {
+        let mut sequence = Summary::new();
+        let mut attack_roll = roll_characteristic(&self.attacks);
+        let attacks = attack_roll.total;
+        if self.attacks.get_count() > 0 {
+            attack_roll.set_label("Attacks");
+            sequence += attack_roll;
+        }
+
+        let (mut hits, hit_count) =
+            self.roll_stage("Hit rolls", attacks, self.skill, self.reroll_hits);
+        hits.add_note(format!("{} of {} attacks hit", hit_count, attacks));
+        sequence += hits;
+
+        let wound_target = get_wound_target(self.strength, self.toughness);
+        let (mut wounds, wound_count) =
+            self.roll_stage("Wound rolls", hit_count, wound_target, self.reroll_wounds);
+        wounds.add_note(format!(
+            "{} of {} hits wound on {}+",
+            wound_count, hit_count, wound_target
+        ));
+
+        let save_target = self.get_save_target();
+        let unsaved = if save_target > 6 {
+            wounds.add_note("No save can be taken".to_string());
+            sequence += wounds;
+            wound_count
+        } else {
+            sequence += wounds;
+            let (mut saves, saved) =
+                self.roll_stage("Saving throws", wound_count, save_target, None);
+            saves.add_note(format!(
+                "{} of {} wounds unsaved against {}+",
+                wound_count - saved,
+                wound_count,
+                save_target
+            ));
+            sequence += saves;
+            wound_count - saved
+        };
+
+        let mut total = 0;
+        let mut slain = 0;
+        let mut current = 0;
+        for i in 1..=unsaved {
+            if self.models.is_some_and(|models| slain >= models) {
+                break;
+            }
+            // flat damage has nothing to show unless feel no pain is rolled against it
+            let mut damage = roll_characteristic(&self.damage);
+            let mut dealt = damage.total;
+            let mut shown = (self.damage.get_count() > 0).then(|| {
+                damage.set_label(&format!("Damage {}", i));
+                damage
+            });
+            if let Some(fnp) = self.fnp.filter(|_| dealt > 0) {
+                let mut ignored = roll_d6s(dealt, fnp);
+                ignored.set_label(&format!("Feel no pain {}", i));
+                ignored.add_note(format!("{} of {} damage ignored", ignored.hits, dealt));
+                dealt -= ignored.hits;
+                shown = Some(match shown {
+                    Some(damage) => damage + ignored,
+                    None => ignored,
+                });
+            }
+            total += dealt;
+            // damage left over once a model is slain is lost
+            current += dealt;
+            if current >= self.wounds {
+                slain += 1;
+                current = 0;
+                if let Some(summary) = shown.as_mut() {
+                    summary.add_note(format!("Model slain ({} so far)", slain));
+                }
+            }
+            if let Some(summary) = shown {
+                sequence += summary;
+            }
+        }
+        (sequence, total, slain)
+    }
+
+    // The damage a single unsaved wound deals once feel no pain has had its go.
+    fn get_damage_dealt(&self) -> Polynomial {
+        let damage = get_distribution(&self.damage);
+        let kept = 1. - self.fnp.map_or(0., get_d6_chance);
+        let mut dealt = Polynomial::new();
+        for (value, p) in damage.get_coefficients() {
+            let mut point = Polynomial::new();
+            point.set_coefficient(0, 1. - kept);
+            point.set_coefficient(1, kept);
+            let mut points = Polynomial::new();
+            points.set_coefficient(0, *p);
+            for _ in 0..*value {
+                points *= point.clone();
+            }
+            dealt += points;
+        }
+        dealt
+    }
+
+    // The exact number of wounds that go unsaved over the whole sequence.
+    pub fn get_unsaved(&self) -> Polynomial {
+        let p = self.get_unsaved_chance();
+        let mut single = Polynomial::new();
+        single.set_coefficient(0, 1. - p);
+        single.set_coefficient(1, p);
+        let mut unsaved = Polynomial::new();
+        for (attacks, q) in get_distribution(&self.attacks).get_coefficients() {
+            let mut wounds = Polynomial::new();
+            wounds.set_coefficient(0, *q);
+            for _ in 0..*attacks {
+                wounds *= single.clone();
+            }
+            unsaved += wounds;
+        }
+        unsaved
+    }
+
+    // The exact number of models slain, following the damage on the model taking it wound by
+    // wound since any left over once it dies is lost.
+    pub fn get_slain(&self) -> Polynomial {
+        let unsaved = self.get_unsaved();
+        let most = unsaved
+            .get_coefficients()
+            .keys()
+            .max()
+            .copied()
+            .unwrap_or(0);
+        let dealt = self.get_damage_dealt();
+        let mut states: HashMap<(u16, u16), f64> = HashMap::from([((0, 0), 1.)]);
+        let mut slain = Polynomial::new();
+        for wounds in 0..=most {
+            let p = unsaved.get_coefficient(wounds);
+            for ((killed, _), q) in &states {
+                let before = slain.get_coefficient(*killed);
+                slain.set_coefficient(*killed, before + p * q);
+            }
+            let mut next = HashMap::new();
+            for ((killed, current), q) in &states {
+                if self.models.is_some_and(|models| *killed >= models) {
+                    *next.entry((*killed, 0)).or_insert(0.) += q;
+                    continue;
+                }
+                for (damage, r) in dealt.get_coefficients() {
+                    let state = if current + damage >= self.wounds {
+                        (killed + 1, 0)
+                    } else {
+                        (*killed, current + damage)
+                    };
+                    *next.entry(state).or_insert(0.) += q * r;
+                }
+            }
+            states = next;
+        }
+        slain
+    }
+}
+
+// Rolls a characteristic like D6 attacks or D3 damage.
+fn roll_characteristic(dice: &DiceGroup) -> Summary {
+    let mut summary = Roller::roll_group(dice.clone(), u16::MAX);
+    summary.set_rules(Rules::Wh40k);
+    summary.hide_hits();
+    summary
+}
+
+// Rolls d6s counting every one at target or more, with no glitches. The notes say how many passed.
+fn roll_d6s(dice: u16, target: u16) -> Summary {
+    let target = target.clamp(2, 7);
+    let dice = DiceGroup::from(&format!("{}d6", dice), 0, target, false).unwrap_or_default();
+    let mut summary = Roller::roll_group(dice, target);
+    summary.set_rules(Rules::Wh40k);
+    summary.hide_hits();
+    summary.hide_total();
+    summary
+}
+
+pub fn get_expected(distribution: &Polynomial) -> f64 {
+    distribution
+        .get_coefficients()
+        .iter()
+        .map(|(value, p)| *value as f64 * p)
+        .sum()
+}
+
+// The chance of each stage going through, and of slaying every number of models.
+pub struct SequenceDisplay {
+    text: String,
+}
+
+impl SequenceDisplay {
+    pub fn new(sequence: &Sequence) -> Self {
+        let wound_target = get_wound_target(sequence.strength, sequence.toughness);
+        let save_target = sequence.get_save_target();
+        let mut text = format!("\n{}\n", "Stage\t\tNeeds\tProbability".bold());
+        for (stage, target, p) in [
+            (
+                "Hit",
+                sequence.skill,
+                Reroll::get_chance(sequence.reroll_hits, sequence.skill),
+            ),
+            (
+                "Wound",
+                wound_target,
+                Reroll::get_chance(sequence.reroll_wounds, wound_target),
+            ),
+            ("Save fails", save_target, 1. - get_d6_chance(save_target)),
+        ] {
+            text += &format!("{:<16}{}+\t{:>8.4} %\n", stage, target.min(7), p * 100.);
+        }
+        text += &format!(
+            "\nExpected unsaved wounds:\t{}\n",
+            format!("{:.2}", get_expected(&sequence.get_unsaved())).bold()
+        );
+
+        let slain = sequence.get_slain();
+        text += &format!("\n{}\n", "Slain\tProbability\tAt least".bold());
+        let most = slain.get_coefficients().keys().max().copied().unwrap_or(0);
+        let mut at_least: f64 = 1.;
+        for models in 0..=most {
+            let p = slain.get_coefficient(models);
+            text += &format!(
+                "{}\t{:>8.4} %\t{:>8.4} %\n",
+                models,
+                p * 100.,
+                at_least.max(0.) * 100.
+            );
+            at_least -= p;
+        }
+        text += &format!(
+            "\nExpected models slain:\t\t{}\n",
+            format!("{:.2}", get_expected(&slain)).bold()
+        );
+        Self { text }
+    }
+}
+
+impl Component for SequenceDisplay {
+    fn draw(&self, mut stdout: &Stdout) -> crossterm::Result<()> {
+        queue!(stdout, Print(self.text.as_str()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(get_wound_target(8, 4), 2);
+        assert_eq!(get_wound_target(5, 4), 3);
+        assert_eq!(get_wound_target(3, 5), 5);
+        assert_eq!(get_wound_target(4, 8), 6);
+
+        let sequence = Sequence {
+            attacks: parse_characteristic("2").unwrap(),
+            skill: 3,
+            strength: 4,
+            toughness: 4,
+            save: 3,
+            ap: 1,
+            invuln: None,
+            damage: parse_characteristic("D3").unwrap(),
+            wounds: 2,
+            models: None,
+            fnp: None,
+            reroll_hits: Some(Reroll::Fails),
+            reroll_wounds: None,
+        };
+        // 8/9 to hit, 1/2 to wound and 1/2 to fail a 4+ save
+        let p = 8. / 9. / 4.;
+        let unsaved = sequence.get_unsaved();
+        assert!((unsaved.get_coefficient(2) - p * p).abs() < 1e-12);
+        // a single D3 slays a 2 wound model two times in three
+        let slain = sequence.get_slain();
+        let one = 2. * p * (1. - p) * 2. / 3. + p * p * 5. / 9.;
+        assert!((slain.get_coefficient(1) - one).abs() < 1e-12);
+        assert!((slain.get_coefficient(2) - p * p * 4. / 9.).abs() < 1e-12);
+
+        // D6-1 damage does nothing on a 1, like the roll
+        let damage = get_distribution(&parse_characteristic("D6-1").unwrap());
+        assert!((damage.get_coefficient(0) - 1. / 6.).abs() < 1e-12);
+        assert!((damage.get_coefficient(5) - 1. / 6.).abs() < 1e-12);
+        assert_eq!(damage.get_coefficient(6), 0.);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::probability::Polynomial;
+
+// The coloured d6s of a Year Zero Engine pool. Sixes are successes on every kind, but a one
+// on a base die is trauma and a one on a gear die degrades the gear.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Base,
+    Skill,
+    Gear,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 3] = [Kind::Base, Kind::Skill, Kind::Gear];
+
+    pub fn get_label(&self) -> &str {
+        match self {
+            Self::Base => "base",
+            Self::Skill => "skill",
+            Self::Gear => "gear",
+        }
+    }
+
+    // A one on a base or gear die is locked in and can't be pushed.
+    pub fn is_bane(&self, value: u16) -> bool {
+        value == 1 && *self != Self::Skill
+    }
+
+    // The chance the die ends on a six, rerolling it once if pushed and it can be.
+    pub fn get_success_chance(&self, pushed: bool) -> f64 {
+        match (pushed, self) {
+            (false, _) => 1. / 6.,
+            (true, Self::Skill) => 1. / 6. + 5. / 6. / 6.,
+            (true, _) => 1. / 6. + 4. / 6. / 6.,
+        }
+    }
+
+    // The chance the die ends on a bane.
+    pub fn get_bane_chance(&self, pushed: bool) -> f64 {
+        match (pushed, self) {
+            (_, Self::Skill) => 0.,
+            (false, _) => 1. / 6.,
+            (true, _) => 1. / 6. + 4. / 6. / 6.,
+        }
+    }
+}
+
+// A pool written as 4b3s2g: four base dice, three skill dice and two gear dice.
+#[derive(Clone, Copy, Default)]
+pub struct Pools {
+    pub base: u16,
+    pub skill: u16,
+    pub gear: u16,
+}
+
+impl Pools {
+    pub fn from(arg: &str) -> Option<Self> {
+        let mut pools = Self::default();
+        let mut count = String::new();
+        for c in arg.chars() {
+            match c {
+                '0'..='9' => count.push(c),
+                'b' | 's' | 'g' => {
+                    let dice: u16 = count.parse().ok()?;
+                    count.clear();
+                    match c {
+                        'b' => pools.base += dice,
+                        's' => pools.skill += dice,
+                        _ => pools.gear += dice,
+                    }
+                }
+                _ => return None,
+            }
+        }
+        if !count.is_empty() {
+            return None;
+        }
+        Some(pools)
+    }
+
+    pub fn get(&self, kind: Kind) -> u16 {
+        match kind {
+            Kind::Base => self.base,
+            Kind::Skill => self.skill,
+            Kind::Gear => self.gear,
+        }
+    }
+
+    pub fn get_count(&self) -> u16 {
+        self.base + self.skill + self.gear
+    }
+
+    // The exact number of successes, before or after pushing every die that can be.
+    pub fn get_successes(&self, pushed: bool) -> Polynomial {
+        let mut result = Polynomial::new();
+        result.set_coefficient(0, 1.);
+        for kind in Kind::ALL {
+            let p = kind.get_success_chance(pushed);
+            let mut die = Polynomial::new();
+            die.set_coefficient(0, 1. - p);
+            die.set_coefficient(1, p);
+            for _ in 0..self.get(kind) {
+                result *= die.clone();
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pools() {
+        let pools = Pools::from("4b3s2g").unwrap();
+        assert_eq!((pools.base, pools.skill, pools.gear), (4, 3, 2));
+        assert!(Pools::from("4b3").is_none());
+        assert!(Pools::from("4k2").is_none());
+
+        let pushed = Pools::from("1b1s").unwrap().get_successes(true);
+        let miss = (1. - 10. / 36.) * (1. - 11. / 36.);
+        assert!((pushed.get_coefficient(0) - miss).abs() < 1e-12);
+    }
+}